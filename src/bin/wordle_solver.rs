@@ -0,0 +1,604 @@
+use clap::{App, Arg};
+use serde_derive::Serialize;
+use std::{fs::File, io::Write, time::Instant};
+use wordle::{
+    display::{self, Color},
+    matrix::PatternMatrix,
+    pattern::{score, Pattern},
+    recommend::{self, Probes, Recommender},
+    shuffle::SplitMix64,
+    strategy::{History, Strategies, Strategy, StrategyOptions},
+    tree::{DecisionTree, Objective, TreeSearch},
+    wordlist,
+};
+
+#[derive(Debug)]
+struct ArgsErr(String);
+impl std::fmt::Display for ArgsErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "ArgsError: {}", self.0)
+    }
+}
+impl std::error::Error for ArgsErr {}
+
+struct WordleSolver {
+    recommender: Recommender,
+    strategy: Box<dyn Strategy>,
+    // None for unlimited guesses
+    max_guesses: Option<u32>,
+    // whether exactly one letter of every pattern is a lie
+    lies: bool,
+    verbose: bool,
+}
+
+impl WordleSolver {
+    fn new(
+        recommender: Recommender,
+        strategy: Box<dyn Strategy>,
+        max_guesses: Option<u32>,
+        lies: bool,
+        verbose: bool,
+    ) -> WordleSolver {
+        WordleSolver {
+            recommender,
+            strategy,
+            max_guesses,
+            lies,
+            verbose,
+        }
+    }
+
+    // the next guess of the strategy after the guesses so far
+    fn recommend_word(&mut self, history: &History) -> Option<String> {
+        let candidates = history.candidates(&self.recommender);
+        if self.verbose {
+            let answers = self.recommender.possible_answers(&candidates);
+            display::println(
+                &format!("Possible answers ({}):", answers.len()),
+                true,
+                Some(true),
+                Some(Color::Blue),
+            );
+            println!("{}", display::preview(&answers, 5));
+            let others = self.recommender.other_words(|w| history.possible(w));
+            if !others.is_empty() {
+                display::println(
+                    &format!("Other acceptable words ({}):", others.len()),
+                    true,
+                    Some(true),
+                    Some(Color::Blue),
+                );
+                println!("{}", display::preview(&others, 5));
+            }
+        }
+
+        let guess = self.strategy.next_guess(&self.recommender, history);
+        match &guess {
+            Some(guess) => display::println(
+                &format!("I recommend you use: {}", guess.to_uppercase()),
+                self.verbose,
+                Some(true),
+                Some(Color::Blue),
+            ),
+            None => display::println(
+                match candidates.is_empty() {
+                    true => "No word in the final set matches the status, please check your input.",
+                    false => {
+                        "The key word can't be found in the guesses left, please check your input."
+                    }
+                },
+                self.verbose,
+                Some(true),
+                Some(Color::Red),
+            ),
+        }
+        guess
+    }
+
+    // play from the opener (or the guess of the strategy), asking the feedback
+    // for every guess, until the key word is found, the feedback ends or there
+    // are no guesses left. Returns the guesses made and whether the last one
+    // was the key word.
+    fn play(
+        &mut self,
+        opener: Option<&str>,
+        mut feedback: impl FnMut(&str) -> Option<Pattern>,
+        limit: Option<u32>,
+    ) -> (Vec<String>, bool) {
+        let mut history = match self.lies {
+            true => History::lying(),
+            false => History::new(),
+        };
+        let mut guesses: Vec<String> = vec![];
+
+        loop {
+            let cnt = guesses.len() as u32 + 1;
+            if limit.is_some_and(|limit| cnt > limit) {
+                return (guesses, false);
+            }
+            let input_word = match (cnt, opener) {
+                (1, Some(opener)) => opener.to_string(),
+                _ => match self.recommend_word(&history) {
+                    Some(word) => word,
+                    None => return (guesses, false),
+                },
+            };
+            guesses.push(input_word.clone());
+            let pattern = match feedback(&input_word) {
+                Some(pattern) => pattern,
+                None => return (guesses, false),
+            };
+            if pattern.is_win() {
+                return (guesses, true);
+            }
+            history.push(&input_word, pattern);
+        }
+    }
+}
+
+// the feedback typed by the player after every guess, None once the input ends
+fn read_pattern(guess: &str) -> Option<Pattern> {
+    display::println(
+        "Please input the status of the last word:",
+        true,
+        Some(true),
+        Some(Color::Blue),
+    );
+    loop {
+        match display::read_line()?.parse::<Pattern>() {
+            Ok(pattern) if pattern.len() == guess.len() => return Some(pattern),
+            Ok(_) => println!("Make sure that the pattern has {} letters.", guess.len()),
+            Err(e) => println!("Make sure that {}.", e),
+        }
+    }
+}
+
+// how the solver did on one answer of the bench
+#[derive(Debug, Serialize)]
+struct BenchResult {
+    answer: String,
+    guesses: u32,
+    solved: bool,
+    path: Vec<String>,
+}
+
+// the guesses the bench makes at least on one answer before giving it up, with
+// unlimited guesses it may try every final word
+const BENCH_LIMIT: u32 = 20;
+
+// play the opener against every final word with the feedback worked out from
+// the answer, print the summary and export the result of every word
+fn bench(
+    solver: &mut WordleSolver,
+    strategy: &str,
+    seed: u64,
+    opener: &str,
+    csv: Option<&str>,
+    json: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if solver.recommender.row(opener).is_none() {
+        Err(ArgsErr(format!(
+            "The opener '{}' is not in the acceptable set.",
+            opener
+        )))?;
+    }
+    let start = Instant::now();
+    let answers: Vec<String> = solver.recommender.final_set().to_vec();
+    let limit = match solver.max_guesses {
+        Some(max) => max.max(BENCH_LIMIT),
+        None => answers.len() as u32 + 1,
+    };
+    let lies = solver.lies;
+    let mut rng = SplitMix64::new(seed);
+    let mut results: Vec<BenchResult> = vec![];
+    for answer in &answers {
+        let feedback = |guess: &str| {
+            let pattern = score(guess, answer);
+            match lies && !pattern.is_win() {
+                true => Some(pattern.lie(&mut rng)),
+                false => Some(pattern),
+            }
+        };
+        let (path, found) = solver.play(Some(opener), feedback, Some(limit));
+        let guesses = path.len() as u32;
+        results.push(BenchResult {
+            answer: answer.to_string(),
+            guesses,
+            solved: found && solver.max_guesses.is_none_or(|max| guesses <= max),
+            path,
+        });
+    }
+    let elapsed = start.elapsed();
+
+    let wins: Vec<&BenchResult> = results.iter().filter(|r| r.solved).collect();
+    let total: u32 = wins.iter().map(|r| r.guesses).sum();
+    println!(
+        "{} with {}: {} words, {} solved ({:.2}%), {:.4} guesses on average, {:.2?} in total",
+        opener.to_uppercase(),
+        strategy,
+        results.len(),
+        wins.len(),
+        wins.len() as f32 * 100.0 / results.len().max(1) as f32,
+        total as f32 / wins.len().max(1) as f32,
+        elapsed
+    );
+    let most = results.iter().map(|r| r.guesses).max().unwrap_or(0);
+    for guesses in 1..=wins.iter().map(|r| r.guesses).max().unwrap_or(0) {
+        let cnt = wins.iter().filter(|r| r.guesses == guesses).count();
+        println!(
+            "{:>2}: {:>5} {}",
+            guesses,
+            cnt,
+            "#".repeat((cnt * 60).div_ceil(results.len().max(1)))
+        );
+    }
+    let failed = results.len() - wins.len();
+    if failed > 0 {
+        println!(" X: {:>5}", failed);
+    }
+    let worst: Vec<&String> = results
+        .iter()
+        .filter(|r| !r.solved || r.guesses == most)
+        .map(|r| &r.answer)
+        .collect();
+    println!(
+        "Hardest words ({} guesses or failed): {}",
+        most,
+        display::preview(&worst, 20)
+    );
+
+    if let Some(path) = csv {
+        let mut file = File::create(path)?;
+        writeln!(file, "answer,guesses,solved,path")?;
+        for r in &results {
+            writeln!(
+                file,
+                "{},{},{},{}",
+                r.answer,
+                r.guesses,
+                r.solved,
+                r.path.join(" ")
+            )?;
+        }
+    }
+    if let Some(path) = json {
+        serde_json::to_writer_pretty(File::create(path)?, &results)?;
+    }
+    Ok(())
+}
+
+// a word list given to the solver, every word must have `length` letters
+fn load_set(path: &str, length: usize) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let words = wordlist::load_words(path)?;
+    if let Some(word) = words.iter().find(|w| w.len() != length) {
+        Err(ArgsErr(format!("Wrong word '{}' in {}.", word, path)))?;
+    }
+    Ok(words)
+}
+
+// print the decision tree of the opener over the final set, and how it does
+fn print_tree(
+    recommender: &Recommender,
+    search: &mut TreeSearch,
+    opener: &str,
+    max_guesses: u32,
+    output: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let opener_row = match recommender.row(opener) {
+        Some(row) => row,
+        None => Err(ArgsErr(format!(
+            "The opener '{}' is not in the acceptable set.",
+            opener
+        )))?,
+    };
+    let candidates = recommender.candidates(|_| true);
+    let tree: DecisionTree = match search.tree(recommender, opener_row, &candidates, max_guesses) {
+        Some(tree) => tree,
+        None => Err(ArgsErr(format!(
+            "Some words can't be found in {} guesses after '{}'.",
+            max_guesses, opener
+        )))?,
+    };
+    match output {
+        Some(path) => File::create(path)?.write_all(tree.to_string().as_bytes())?,
+        None => print!("{}", tree),
+    }
+    let depths = tree.depths();
+    let total: u32 = depths.iter().sum();
+    eprintln!(
+        "{}: {} words, {} guesses in total, {:.4} on average, {} at most",
+        opener.to_uppercase(),
+        depths.len(),
+        total,
+        total as f32 / depths.len() as f32,
+        depths.iter().max().unwrap_or(&0)
+    );
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let matches = App::new("Wordle Solver")
+        .version("0.1.0")
+        .author("Jashng")
+        .about("A wordle solver recommending the next word to guess.")
+        .arg(
+            Arg::with_name("optimal")
+                .global(true)
+                .short('O')
+                .long("optimal")
+                .takes_value(false)
+                .help("Toggle to recommend words by searching the decision tree, the same as --strategy optimal."),
+        )
+        .arg(
+            Arg::with_name("strategy")
+                .global(true)
+                .long("strategy")
+                .takes_value(true)
+                .default_value("entropy")
+                .help("The strategy recommending words: entropy, minimax, expected, frequency, random or optimal."),
+        )
+        .arg(
+            Arg::with_name("seed")
+                .global(true)
+                .long("seed")
+                .takes_value(true)
+                .default_value("0")
+                .help("The seed of the random strategy and of the lies of the bench."),
+        )
+        .arg(
+            Arg::with_name("lie")
+                .global(true)
+                .long("lie")
+                .takes_value(false)
+                .help("Toggle to solve games where exactly one letter of every pattern has a wrong color."),
+        )
+        .arg(
+            Arg::with_name("probes")
+                .global(true)
+                .long("probes")
+                .takes_value(true)
+                .possible_values(["any", "candidates"])
+                .default_value("any")
+                .help("Recommend any acceptable word or only the possible key words."),
+        )
+        .arg(
+            Arg::with_name("weights_file")
+                .global(true)
+                .long("weights")
+                .takes_value(true)
+                .help("The file of the weight of every final word, one \"word weight\" per line."),
+        )
+        .arg(
+            Arg::with_name("tree")
+                .short('T')
+                .long("tree")
+                .takes_value(true)
+                .help("Print the complete decision tree of the opening word and exit."),
+        )
+        .arg(
+            Arg::with_name("output")
+                .short('o')
+                .long("output")
+                .takes_value(true)
+                .help("The file to write the decision tree to."),
+        )
+        .arg(
+            Arg::with_name("objective")
+                .global(true)
+                .long("objective")
+                .takes_value(true)
+                .possible_values(["expected", "worst"])
+                .default_value("expected")
+                .help("Minimize the expected guesses or the worst-case guesses in the tree search."),
+        )
+        .arg(
+            Arg::with_name("width")
+                .global(true)
+                .long("width")
+                .takes_value(true)
+                .default_value("10")
+                .help("The number of most promising guesses tried at every node of the tree search."),
+        )
+        .arg(
+            Arg::with_name("max_guesses")
+                .global(true)
+                .long("max-guesses")
+                .alias("depth")
+                .takes_value(true)
+                .default_value("6")
+                .help("The number of guesses of a game or 'unlimited', the tree search finds every word in them."),
+        )
+        .arg(
+            Arg::with_name("final_set_file")
+                .global(true)
+                .short('f')
+                .long("final-set")
+                .takes_value(true)
+                .help("The file of the possible key words, which must be acceptable words."),
+        )
+        .arg(
+            Arg::with_name("acceptable_set_file")
+                .global(true)
+                .short('a')
+                .long("acceptable-set")
+                .takes_value(true)
+                .help("The file of the words the solver may guess."),
+        )
+        .arg(
+            Arg::with_name("length")
+                .global(true)
+                .long("length")
+                .takes_value(true)
+                .default_value("5")
+                .help("The number of letters of the words, from 4 to 11. Lengths other than 5 need both word set files."),
+        )
+        .subcommand(
+            App::new("bench")
+                .about("Play the solver against every final word and report how it does.")
+                .arg(
+                    Arg::with_name("opener")
+                        .long("opener")
+                        .takes_value(true)
+                        .default_value("salet")
+                        .help("The opening word of every game."),
+                )
+                .arg(
+                    Arg::with_name("csv")
+                        .long("csv")
+                        .takes_value(true)
+                        .help("The file to write the result of every word to as CSV."),
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .takes_value(true)
+                        .help("The file to write the result of every word to as JSON."),
+                ),
+        )
+        .get_matches();
+    let bench_args = matches.subcommand_matches("bench");
+    let cli_args = bench_args.unwrap_or(&matches);
+
+    let probes: Probes = cli_args.value_of("probes").unwrap().parse()?;
+    let objective: Objective = cli_args.value_of("objective").unwrap().parse()?;
+    let width: usize = match cli_args.value_of("width").unwrap().parse() {
+        Ok(w) => w,
+        Err(_) => Err(ArgsErr("The format of --width is wrong.".to_string()))?,
+    };
+    let max_guesses: Option<u32> = match cli_args.value_of("max_guesses").unwrap() {
+        "unlimited" => None,
+        m => match m.parse() {
+            Ok(0) => None,
+            Ok(m) => Some(m),
+            Err(_) => Err(ArgsErr(
+                "--max-guesses must be a positive number or 'unlimited'.".to_string(),
+            ))?,
+        },
+    };
+    let seed: u64 = match cli_args.value_of("seed").unwrap().parse() {
+        Ok(s) => s,
+        Err(_) => Err(ArgsErr("The format of --seed is wrong.".to_string()))?,
+    };
+    let lies = cli_args.is_present("lie");
+    let strategy_name = match cli_args.is_present("optimal") {
+        true => "optimal",
+        false => cli_args.value_of("strategy").unwrap(),
+    };
+    let length: usize = match cli_args.value_of("length").unwrap().parse() {
+        Ok(l) if (Pattern::MIN_LEN..=Pattern::MAX_LEN).contains(&l) => l,
+        _ => Err(ArgsErr(
+            "--length must be a number from 4 to 11.".to_string(),
+        ))?,
+    };
+    if length != Pattern::DEFAULT_LEN
+        && !(cli_args.is_present("final_set_file") && cli_args.is_present("acceptable_set_file"))
+    {
+        Err(ArgsErr(
+            "The built-in word sets only have 5-letter words, give both -f/--final-set and -a/--acceptable-set for other lengths.".to_string(),
+        ))?;
+    }
+    let mut final_set = wordlist::builtin_final();
+    let mut acceptable_set = wordlist::builtin_acceptable();
+    if let Some(path) = cli_args.value_of("final_set_file") {
+        final_set = load_set(path, length)?;
+    }
+    if let Some(path) = cli_args.value_of("acceptable_set_file") {
+        acceptable_set = load_set(path, length)?;
+    }
+    if let Some(word) = final_set
+        .iter()
+        .find(|w| acceptable_set.binary_search(w).is_err())
+    {
+        Err(ArgsErr(format!(
+            "The final word '{}' is not in the acceptable set.",
+            word
+        )))?;
+    }
+    let mut recommender = Recommender::new(
+        final_set,
+        acceptable_set,
+        &PatternMatrix::default_cache_dir(),
+    )?;
+    if let Some(path) = cli_args.value_of("weights_file") {
+        recommender.set_weights(&recommend::load_weights(path)?);
+    }
+    // guessing the possible words one by one finds any of them, so unlimited
+    // guesses never need more than the size of the final set
+    let depth = max_guesses.unwrap_or(recommender.final_set().len() as u32);
+    let strategy = Strategies::new()
+        .create(
+            strategy_name,
+            &StrategyOptions {
+                probes,
+                objective,
+                width,
+                max_guesses: depth,
+                seed,
+            },
+        )
+        .map_err(ArgsErr)?;
+    if let Some(bench_args) = bench_args {
+        let mut wordle_solver = WordleSolver::new(recommender, strategy, max_guesses, lies, false);
+        return bench(
+            &mut wordle_solver,
+            strategy_name,
+            seed,
+            &bench_args.value_of("opener").unwrap().to_lowercase(),
+            bench_args.value_of("csv"),
+            bench_args.value_of("json"),
+        );
+    }
+    // -T/--tree and -o/--output aren't arguments of the bench
+    if let Some(opener) = matches.value_of("tree") {
+        return print_tree(
+            &recommender,
+            &mut TreeSearch::new(objective, width),
+            &opener.to_lowercase(),
+            depth,
+            matches.value_of("output"),
+        );
+    }
+
+    let mut words: Vec<String> = vec![
+        "salet".to_string(),
+        "reast".to_string(),
+        "crate".to_string(),
+        "trace".to_string(),
+        "slate".to_string(),
+        "crane".to_string(),
+    ];
+    // the openers only fit the built-in word length
+    words.retain(|w| recommender.row(w).is_some());
+    println!("Welcome to wordle solver.");
+    println!("Pick a word from below (or any acceptable word) and start your game,");
+    println!("or just press Enter to let the solver pick:");
+    for (index, word) in words.iter().enumerate() {
+        print!(
+            "{}{}",
+            match index {
+                0 => "",
+                _ => ", ",
+            },
+            word.to_uppercase()
+        );
+    }
+    println!();
+    let mut input_word: String = display::read().to_lowercase();
+    while !input_word.is_empty() && recommender.row(&input_word).is_none() {
+        println!("Make sure that the word you input is an acceptable word.");
+        input_word = display::read().to_lowercase();
+    }
+    let opener = match input_word.is_empty() {
+        true => None,
+        false => Some(input_word.as_str()),
+    };
+    let mut wordle_solver = WordleSolver::new(recommender, strategy, max_guesses, lies, true);
+    match wordle_solver.play(opener, read_pattern, max_guesses) {
+        (_, true) => println!("SUCCESS!"),
+        (guesses, false) if max_guesses.is_some_and(|max| guesses.len() as u32 >= max) => {
+            println!("FAILED, there are no guesses left.")
+        }
+        _ => {}
+    }
+    Ok(())
+}
@@ -0,0 +1,236 @@
+use crate::{pattern::Pattern, wordset::WordSet};
+use std::fmt;
+
+// the index of a lowercase letter in the alphabet
+fn letter(b: u8) -> Option<usize> {
+    match b {
+        b'a'..=b'z' => Some((b - b'a') as usize),
+        _ => None,
+    }
+}
+
+// what the key word must look like after the feedback of previous guesses,
+// with letters as bits of a u32 and letter counts indexed by the alphabet.
+// The guesses and the words checked are all of the same length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Constraints {
+    green: [u32; Pattern::MAX_LEN],
+    yellow: [u32; Pattern::MAX_LEN],
+    forbid: [u32; Pattern::MAX_LEN],
+    min: [u8; 26],
+    max: [u8; 26],
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Constraints::new()
+    }
+}
+
+impl Constraints {
+    pub fn new() -> Constraints {
+        Constraints {
+            green: [0; Pattern::MAX_LEN],
+            yellow: [0; Pattern::MAX_LEN],
+            forbid: [0; Pattern::MAX_LEN],
+            min: [0; 26],
+            max: [Pattern::MAX_LEN as u8; 26],
+        }
+    }
+
+    // record the pattern the guess got
+    pub fn apply(&mut self, guess: &str, pattern: Pattern) {
+        let mut known = [0u8; 26];
+        let mut grey: u32 = 0;
+        for (i, &b) in guess.as_bytes().iter().take(Pattern::MAX_LEN).enumerate() {
+            let l = match letter(b) {
+                Some(l) => l,
+                None => continue,
+            };
+            match pattern.digit(i) {
+                2 => {
+                    self.green[i] = 1 << l;
+                    known[l] += 1;
+                }
+                1 => {
+                    self.yellow[i] |= 1 << l;
+                    self.forbid[i] |= 1 << l;
+                    known[l] += 1;
+                }
+                _ => {
+                    self.forbid[i] |= 1 << l;
+                    grey |= 1 << l;
+                }
+            }
+        }
+        for (l, &k) in known.iter().enumerate() {
+            self.min[l] = self.min[l].max(k);
+            if grey >> l & 1 == 1 {
+                self.max[l] = self.max[l].min(k);
+            }
+        }
+    }
+
+    // whether the word could still be the key word
+    pub fn matches(&self, word: &str) -> bool {
+        let word = word.as_bytes();
+        if word.len() > Pattern::MAX_LEN {
+            return false;
+        }
+        let mut counts = [0u8; 26];
+        for (i, &b) in word.iter().enumerate() {
+            let l = match letter(b) {
+                Some(l) => l,
+                None => return false,
+            };
+            if (self.green[i] != 0 && self.green[i] != 1 << l) || self.forbid[i] >> l & 1 == 1 {
+                return false;
+            }
+            counts[l] += 1;
+        }
+        (0..26).all(|l| self.min[l] <= counts[l] && counts[l] <= self.max[l])
+    }
+
+    // the words in the list which could still be the key word
+    pub fn filter(&self, words: &[String]) -> WordSet {
+        let mut set = WordSet::new(words.len());
+        for (i, word) in words.iter().enumerate() {
+            if self.matches(word) {
+                set.insert(i);
+            }
+        }
+        set
+    }
+
+    // remove the words which could no longer be the key word from the set
+    pub fn retain(&self, words: &[String], set: &mut WordSet) {
+        for i in set.clone().iter() {
+            if !self.matches(&words[i]) {
+                set.remove(i);
+            }
+        }
+    }
+
+    // whether the guess is allowed in hard mode: greens stay in place, yellows
+    // are not reused where they were yellow and every letter found is used as
+    // many times as it is known to be in the key word. Ultra hard mode also
+    // rejects letters known to be absent and any position known to be wrong.
+    pub fn check_hard(&self, word: &str, ultra: bool) -> Result<(), HardModeErr> {
+        let word = word.as_bytes();
+        let mut counts = [0u8; 26];
+        for (i, &b) in word.iter().take(Pattern::MAX_LEN).enumerate() {
+            let c = b as char;
+            if let Some(g) = self.green(i) {
+                if g != c {
+                    return Err(HardModeErr::Green(i, g));
+                }
+            }
+            if let Some(l) = letter(b) {
+                if self.yellow[i] >> l & 1 == 1 {
+                    return Err(HardModeErr::Yellow(i, c));
+                }
+                counts[l] += 1;
+            }
+        }
+        for (l, &count) in counts.iter().enumerate() {
+            if count < self.min[l] {
+                return Err(HardModeErr::TooFew((b'a' + l as u8) as char, self.min[l]));
+            }
+        }
+        if ultra {
+            for (l, &count) in counts.iter().enumerate() {
+                if count > self.max[l] {
+                    return Err(HardModeErr::TooMany((b'a' + l as u8) as char, self.max[l]));
+                }
+            }
+            for (i, &b) in word.iter().take(Pattern::MAX_LEN).enumerate() {
+                if self.forbidden(i, b as char) {
+                    return Err(HardModeErr::Forbidden(i, b as char));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // the letter known to be at the position
+    pub fn green(&self, i: usize) -> Option<char> {
+        match self.green[i] {
+            0 => None,
+            bit => Some((b'a' + bit.trailing_zeros() as u8) as char),
+        }
+    }
+
+    // whether the letter is known not to be at the position
+    pub fn forbidden(&self, i: usize, c: char) -> bool {
+        letter(c as u8).is_some_and(|l| self.forbid[i] >> l & 1 == 1)
+    }
+
+    // the least number of the letter in the key word
+    pub fn min_count(&self, c: char) -> u8 {
+        letter(c as u8).map_or(0, |l| self.min[l])
+    }
+
+    // the most number of the letter in the key word
+    pub fn max_count(&self, c: char) -> u8 {
+        letter(c as u8).map_or(0, |l| self.max[l])
+    }
+}
+
+// the rule of hard mode a guess breaks, positions count from 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HardModeErr {
+    // the letter was green at the position
+    Green(usize, char),
+    // the letter was yellow at the position
+    Yellow(usize, char),
+    // the key word has at least this many of the letter
+    TooFew(char, u8),
+    // the key word has at most this many of the letter
+    TooMany(char, u8),
+    // the letter is known not to be at the position
+    Forbidden(usize, char),
+}
+
+impl fmt::Display for HardModeErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let times = |n: u8| match n {
+            1 => "once".to_string(),
+            2 => "twice".to_string(),
+            _ => format!("{} times", n),
+        };
+        match *self {
+            HardModeErr::Green(i, c) => {
+                write!(f, "Letter {} must be {}.", i + 1, c.to_ascii_uppercase())
+            }
+            HardModeErr::Yellow(i, c) => write!(
+                f,
+                "{} was already yellow at letter {}, move it.",
+                c.to_ascii_uppercase(),
+                i + 1
+            ),
+            HardModeErr::TooFew(c, n) => write!(
+                f,
+                "Guess must contain {} at least {}.",
+                c.to_ascii_uppercase(),
+                times(n)
+            ),
+            HardModeErr::TooMany(c, 0) => {
+                write!(f, "{} is not in the key word.", c.to_ascii_uppercase())
+            }
+            HardModeErr::TooMany(c, n) => write!(
+                f,
+                "Guess can contain {} at most {}.",
+                c.to_ascii_uppercase(),
+                times(n)
+            ),
+            HardModeErr::Forbidden(i, c) => write!(
+                f,
+                "{} is known not to be letter {}.",
+                c.to_ascii_uppercase(),
+                i + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HardModeErr {}
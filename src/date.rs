@@ -0,0 +1,129 @@
+use std::{
+    fmt,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+// a day of the calendar, counted in days from 1970-01-01
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    days: i64,
+}
+
+impl Date {
+    // the date of the first wordle
+    pub const WORDLE_EPOCH: Date = Date { days: 18797 };
+
+    // None if there is no such day, like 2022-02-30
+    pub fn from_ymd(year: i64, month: u32, day: u32) -> Option<Date> {
+        if !(1..=12).contains(&month) || day < 1 || day > Date::month_len(year, month) {
+            return None;
+        }
+        // count the years from march, so the leap day is the last day of a year
+        let (y, m) = match month {
+            1 | 2 => (year - 1, month as i64 + 9),
+            _ => (year, month as i64 - 3),
+        };
+        let era = y.div_euclid(400);
+        let year_of_era = y.rem_euclid(400);
+        let day_of_year = (153 * m + 2) / 5 + day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        Some(Date {
+            days: era * 146097 + day_of_era - 719468,
+        })
+    }
+
+    pub fn ymd(&self) -> (i64, u32, u32) {
+        let days = self.days + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days.rem_euclid(146097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let m = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * m + 2) / 5 + 1) as u32;
+        let month = match m {
+            0..=9 => m + 3,
+            _ => m - 9,
+        } as u32;
+        let year = year_of_era + era * 400 + (month <= 2) as i64;
+        (year, month, day)
+    }
+
+    // the day it is now in UTC
+    pub fn today() -> Date {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |t| t.as_millis() as u64);
+        Date::from_unix_millis(millis)
+    }
+
+    // the day of a unix time in UTC
+    pub fn from_unix_millis(millis: u64) -> Date {
+        Date {
+            days: (millis / 86_400_000) as i64,
+        }
+    }
+
+    pub fn add_days(&self, days: i64) -> Date {
+        Date {
+            days: self.days + days,
+        }
+    }
+
+    // the days from the other date to this one, negative if this one is earlier
+    pub fn days_since(&self, other: Date) -> i64 {
+        self.days - other.days
+    }
+
+    fn month_len(year: i64, month: u32) -> u32 {
+        match month {
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.ymd();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseDateErr;
+
+impl fmt::Display for ParseDateErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a date should be a day of the calendar like 2022-01-31")
+    }
+}
+
+impl std::error::Error for ParseDateErr {}
+
+impl FromStr for Date {
+    type Err = ParseDateErr;
+
+    // parse a date like "2022-01-31"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split('-').collect();
+        if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2 {
+            return Err(ParseDateErr);
+        }
+        let number = |p: &str| -> Result<u32, ParseDateErr> {
+            match p.bytes().all(|b| b.is_ascii_digit()) {
+                true => p.parse().map_err(|_| ParseDateErr),
+                false => Err(ParseDateErr),
+            }
+        };
+        Date::from_ymd(
+            number(parts[0])? as i64,
+            number(parts[1])?,
+            number(parts[2])?,
+        )
+        .ok_or(ParseDateErr)
+    }
+}
@@ -0,0 +1,106 @@
+use std::{
+    io::{self, BufRead, Write},
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Mutex, OnceLock,
+    },
+    thread,
+    time::Duration,
+};
+
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+    Yellow,
+    Nothing,
+}
+
+pub fn printall(pln: bool, words: &str, tty: bool, bold: Option<bool>, color: Option<Color>) {
+    if tty {
+        // color: 0->nothing 1->red 2->green 3->blue 4->yellow
+        let bd: bool = bold.unwrap_or(false);
+        let col: Color = color.unwrap_or(Color::Nothing);
+        let mut stl = console::style(words.to_string());
+        if bd {
+            stl = stl.bold();
+        }
+        match col {
+            Color::Red => stl = stl.red(),
+            Color::Green => stl = stl.green(),
+            Color::Blue => stl = stl.blue(),
+            Color::Yellow => stl = stl.yellow(),
+            _ => {}
+        };
+        match pln {
+            true => println!("{}", stl),
+            false => print!("{}", stl),
+        };
+        io::stdout().flush().unwrap();
+    }
+}
+
+pub fn println(words: &str, tty: bool, bold: Option<bool>, color: Option<Color>) {
+    printall(true, words, tty, bold, color);
+}
+
+pub fn print(words: &str, tty: bool, bold: Option<bool>, color: Option<Color>) {
+    printall(false, words, tty, bold, color);
+}
+
+pub fn testout(words: &str, tty: bool) {
+    if !tty {
+        print!("{}", words);
+    }
+}
+
+// the lines of the standard input, read by a thread of their own so that
+// reading one can time out
+fn lines() -> &'static Mutex<Receiver<String>> {
+    static LINES: OnceLock<Mutex<Receiver<String>>> = OnceLock::new();
+    LINES.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Mutex::new(receiver)
+    })
+}
+
+// the next line trimmed, empty at the end of the input
+pub fn read() -> String {
+    read_line().unwrap_or_default()
+}
+
+// the next line trimmed, None at the end of the input
+pub fn read_line() -> Option<String> {
+    let line = lines().lock().unwrap().recv().ok()?;
+    Some(line.trim().to_string())
+}
+
+// the next line like read(), None if it isn't typed in time
+pub fn read_timeout(timeout: Duration) -> Option<String> {
+    match lines().lock().unwrap().recv_timeout(timeout) {
+        Ok(line) => Some(line.trim().to_string()),
+        Err(RecvTimeoutError::Disconnected) => Some(String::new()),
+        Err(RecvTimeoutError::Timeout) => None,
+    }
+}
+
+// the first few words in upper case, like "CRANE SLATE TRACE..."
+pub fn preview(words: &[&String], limit: usize) -> String {
+    let mut line = words
+        .iter()
+        .take(limit)
+        .map(|w| w.to_uppercase())
+        .collect::<Vec<String>>()
+        .join(" ");
+    if words.len() > limit {
+        line += "...";
+    }
+    line
+}
@@ -0,0 +1,249 @@
+use crate::{
+    date::Date,
+    pattern::score,
+    state::{Game, Mode, State},
+};
+use serde_derive::Serialize;
+use std::{
+    io::{self, Write},
+    str::FromStr,
+};
+
+// how the rows are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    // one JSON object per line
+    Jsonl,
+    // a Markdown table
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "jsonl" => Ok(Format::Jsonl),
+            "markdown" => Ok(Format::Markdown),
+            _ => Err(format!(
+                "Unknown format '{}', use 'csv', 'jsonl' or 'markdown'.",
+                s
+            )),
+        }
+    }
+}
+
+// a row of the table, which is a JSON object in JSON Lines
+pub trait Row: serde::Serialize {
+    const HEADER: &'static [&'static str];
+
+    // the fields in the order of the header
+    fn fields(&self) -> Vec<String>;
+}
+
+// the games exported, all of them by default
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    // the days of the games, both included
+    pub from: Option<Date>,
+    pub to: Option<Date>,
+    // the player of --player, "(default)" for the games played without it
+    pub player: Option<String>,
+}
+
+impl Filter {
+    fn keeps(&self, player: &str, day: Option<Date>) -> bool {
+        self.player.as_ref().is_none_or(|p| p == player)
+            && self.from.is_none_or(|from| day.is_some_and(|d| d >= from))
+            && self.to.is_none_or(|to| day.is_some_and(|d| d <= to))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GameRow {
+    pub player: String,
+    pub id: String,
+    pub date: String,
+    pub answer: String,
+    pub guesses: String,
+    pub result: String,
+    pub guess_count: usize,
+    pub mode: String,
+}
+
+impl Row for GameRow {
+    const HEADER: &'static [&'static str] = &[
+        "player",
+        "id",
+        "date",
+        "answer",
+        "guesses",
+        "result",
+        "guess_count",
+        "mode",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.player.to_string(),
+            self.id.to_string(),
+            self.date.to_string(),
+            self.answer.to_string(),
+            self.guesses.to_string(),
+            self.result.to_string(),
+            self.guess_count.to_string(),
+            self.mode.to_string(),
+        ]
+    }
+}
+
+// a guess of a game, with its pattern on every board
+#[derive(Debug, Clone, Serialize)]
+pub struct GuessRow {
+    pub player: String,
+    pub id: String,
+    pub date: String,
+    pub answer: String,
+    pub index: usize,
+    pub guess: String,
+    pub pattern: String,
+}
+
+impl Row for GuessRow {
+    const HEADER: &'static [&'static str] = &[
+        "player", "id", "date", "answer", "index", "guess", "pattern",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.player.to_string(),
+            self.id.to_string(),
+            self.date.to_string(),
+            self.answer.to_string(),
+            self.index.to_string(),
+            self.guess.to_string(),
+            self.pattern.to_string(),
+        ]
+    }
+}
+
+// the date of the puzzle, else the day the game was over in UTC
+fn day(game: &Game) -> Option<Date> {
+    match &game.date {
+        Some(date) => date.parse().ok(),
+        None => game.finished.map(Date::from_unix_millis),
+    }
+}
+
+// the games of every profile the filter keeps, with their player and day
+fn games<'a>(state: &'a State, filter: &Filter) -> Vec<(String, Option<Date>, &'a Game)> {
+    let mut games = vec![];
+    for (name, profile) in state.profiles() {
+        let player = name.unwrap_or("(default)");
+        for game in &profile.games {
+            if filter.keeps(player, day(game)) {
+                games.push((player.to_string(), day(game), game));
+            }
+        }
+    }
+    games
+}
+
+pub fn game_rows(state: &State, filter: &Filter) -> Vec<GameRow> {
+    games(state, filter)
+        .into_iter()
+        .map(|(player, day, game)| GameRow {
+            player,
+            id: game.id.to_string(),
+            date: day.map_or(String::new(), |d| d.to_string()),
+            answer: answers(game),
+            guesses: game.guesses.join(" "),
+            result: match game.won() {
+                true => "won".to_string(),
+                false => "lost".to_string(),
+            },
+            guess_count: game.guesses.len(),
+            mode: match game.mode {
+                Some(Mode::Word) => "word",
+                Some(Mode::Random) => "random",
+                Some(Mode::Date) => "date",
+                Some(Mode::Evil) => "evil",
+                None => "",
+            }
+            .to_string(),
+        })
+        .collect()
+}
+
+// the patterns are the true ones, a lying game showed other ones
+pub fn guess_rows(state: &State, filter: &Filter) -> Vec<GuessRow> {
+    let mut rows = vec![];
+    for (player, day, game) in games(state, filter) {
+        for (i, guess) in game.guesses.iter().enumerate() {
+            let patterns: Vec<String> = game
+                .all_answers()
+                .iter()
+                .map(|answer| match guess.len() == answer.len() {
+                    true => score(&guess.to_lowercase(), &answer.to_lowercase()).to_string(),
+                    false => String::new(),
+                })
+                .collect();
+            rows.push(GuessRow {
+                player: player.to_string(),
+                id: game.id.to_string(),
+                date: day.map_or(String::new(), |d| d.to_string()),
+                answer: answers(game),
+                index: i + 1,
+                guess: guess.to_string(),
+                pattern: patterns.join(" "),
+            });
+        }
+    }
+    rows
+}
+
+fn answers(game: &Game) -> String {
+    game.all_answers()
+        .iter()
+        .map(|a| a.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+pub fn write<R: Row>(rows: &[R], format: Format, out: &mut impl Write) -> io::Result<()> {
+    match format {
+        Format::Csv => {
+            writeln!(out, "{}", R::HEADER.join(","))?;
+            for row in rows {
+                let fields: Vec<String> = row.fields().iter().map(|f| csv_field(f)).collect();
+                writeln!(out, "{}", fields.join(","))?;
+            }
+        }
+        Format::Jsonl => {
+            for row in rows {
+                serde_json::to_writer(&mut *out, row)?;
+                writeln!(out)?;
+            }
+        }
+        Format::Markdown => {
+            writeln!(out, "| {} |", R::HEADER.join(" | "))?;
+            writeln!(out, "|{}", " --- |".repeat(R::HEADER.len()))?;
+            for row in rows {
+                let fields: Vec<String> =
+                    row.fields().iter().map(|f| f.replace('|', "\\|")).collect();
+                writeln!(out, "| {} |", fields.join(" | "))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// a field quoted when it has a comma, a quote or a line break
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{
+    constraints::Constraints,
+    display::Color,
+    pattern::{score, Pattern},
+};
+use std::collections::HashMap;
+
+pub const ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AlphStatus {
+    //the status of alphabet
+    Right,
+    PosWrong,
+    TooMany,
+    Unknown,
+}
+
+impl AlphStatus {
+    pub fn parse1(&self) -> u32 {
+        //parse for comparing priority
+        match &self {
+            AlphStatus::Right => 3,
+            AlphStatus::PosWrong => 2,
+            AlphStatus::TooMany => 1,
+            AlphStatus::Unknown => 0,
+        }
+    }
+
+    pub fn parse2(&self) -> Color {
+        //parse for getting color code (None,R,G,B,Y)->(0,1,2,3,4)
+        match &self {
+            AlphStatus::Right => Color::Green,
+            AlphStatus::PosWrong => Color::Yellow,
+            AlphStatus::TooMany => Color::Red,
+            AlphStatus::Unknown => Color::Nothing,
+        }
+    }
+
+    pub fn parse3(&self) -> String {
+        //parse for getting status G,Y,R,X
+        match &self {
+            AlphStatus::Right => "G".to_string(),    //Green
+            AlphStatus::PosWrong => "Y".to_string(), //Yellow
+            AlphStatus::TooMany => "R".to_string(),  //Red
+            AlphStatus::Unknown => "X".to_string(),  //Unknown
+        }
+    }
+}
+
+// everything we have learned about the key word from previous guesses
+#[derive(Debug, Clone)]
+pub struct Knowledge {
+    pub status: HashMap<char, AlphStatus>,
+    pub constraints: Constraints,
+    // every guess and the pattern it got
+    pub rows: Vec<(String, Pattern)>,
+    // whether exactly one letter of every pattern is a lie, then the status
+    // and the constraints are only what the patterns show
+    pub lies: bool,
+}
+
+impl Default for Knowledge {
+    fn default() -> Self {
+        Knowledge::new()
+    }
+}
+
+impl Knowledge {
+    pub fn new() -> Knowledge {
+        let mut status = HashMap::new();
+        for c in ALPHABET.chars() {
+            status.insert(c, AlphStatus::Unknown);
+        }
+        Knowledge {
+            status,
+            constraints: Constraints::new(),
+            rows: vec![],
+            lies: false,
+        }
+    }
+
+    // the knowledge of a game where every pattern has exactly one lie
+    pub fn lying() -> Knowledge {
+        Knowledge {
+            lies: true,
+            ..Knowledge::new()
+        }
+    }
+
+    // record what the pattern of the input word tells about the key word
+    pub fn update(&mut self, input_word: &str, pattern: Pattern) -> Vec<AlphStatus> {
+        let curstatus = pattern.to_status();
+        self.constraints.apply(input_word, pattern);
+        self.rows.push((input_word.to_string(), pattern));
+        for (c, newstatus) in input_word.chars().zip(curstatus.iter()) {
+            let oldstatus: &AlphStatus = self.status.get(&c).unwrap();
+            if oldstatus.parse1() < newstatus.parse1() {
+                self.status.insert(c, *newstatus);
+            }
+        }
+        curstatus
+    }
+
+    // whether the input could still be the key word
+    pub fn check_possible(&self, input: &str) -> bool {
+        match self.lies {
+            true => fits_one_lie(input, &self.rows),
+            false => self.constraints.matches(input),
+        }
+    }
+}
+
+// whether the word could be the key word if every row but the winning one has
+// exactly one letter of a wrong color. A lie is never all green, so a row which
+// isn't rules out its guess.
+pub fn fits_one_lie(word: &str, rows: &[(String, Pattern)]) -> bool {
+    rows.iter().all(|(guess, pattern)| {
+        let truth = score(guess, word);
+        match pattern.is_win() {
+            true => truth == *pattern,
+            false => !truth.is_win() && truth.distance(pattern) == 1,
+        }
+    })
+}
@@ -0,0 +1,15 @@
+pub mod builtin_words;
+pub mod constraints;
+pub mod date;
+pub mod display;
+pub mod export;
+pub mod feedback;
+pub mod matrix;
+pub mod pattern;
+pub mod recommend;
+pub mod shuffle;
+pub mod state;
+pub mod strategy;
+pub mod tree;
+pub mod wordlist;
+pub mod wordset;
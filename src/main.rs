@@ -1,48 +1,30 @@
-mod builtin_words;
-
 use clap::{App, Arg, ArgMatches};
-use console;
-use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
-use serde_derive::{Deserialize, Serialize};
-use serde_json;
+use serde_derive::Deserialize;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     fs::File,
-    io::{self, BufRead, BufReader, Write},
-    path::Path,
+    io::{self, BufReader, BufWriter, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use wordle::{
+    constraints::{Constraints, HardModeErr},
+    date::Date,
+    display::{self, Color},
+    export::{self, Filter, Format},
+    feedback::{AlphStatus, Knowledge, ALPHABET},
+    matrix::PatternMatrix,
+    pattern::{score, Pattern},
+    recommend::{self, Probes, Recommender},
+    shuffle::{self, SplitMix64},
+    state::{Game, Mode, State, Stats},
+    wordlist,
 };
-
-#[derive(Debug, Serialize, Deserialize, Default)]
-#[serde(default)]
-struct Game {
-    answer: String,
-    guesses: Vec<String>,
-}
-
-impl Game {
-    fn new() -> Game {
-        Game {
-            answer: "".to_string(),
-            guesses: vec![],
-        }
-    }
-}
-
-#[derive(Debug, Serialize, Deserialize, Default)]
-#[serde(default)]
-struct State {
-    total_rounds: u32,
-    games: Vec<Game>,
-}
-
-impl State {
-    fn new() -> State {
-        State {
-            total_rounds: 0,
-            games: vec![],
-        }
-    }
-}
 
 #[derive(Debug)]
 struct ArgsErr<'a>(&'a str);
@@ -53,326 +35,203 @@ impl std::fmt::Display for ArgsErr<'_> {
 }
 impl std::error::Error for ArgsErr<'_> {}
 
-enum Color {
-    Red,
-    Green,
-    Blue,
-    Yellow,
-    Nothing,
-}
+// keeps the round being played
+type SaveRound<'a> = &'a mut dyn FnMut(&Game) -> Result<(), Box<dyn std::error::Error>>;
 
-#[derive(Copy, Clone, Debug, PartialEq)]
-enum AlphStatus {
-    //the status of alphabet
-    Right,
-    PosWrong,
-    TooMany,
-    Unknown,
+// the time limits of a timed game in seconds, None for no limit
+#[derive(Debug, Clone, Copy, Default)]
+struct TimeLimits {
+    total: Option<u64>,
+    per_guess: Option<u64>,
 }
 
-impl AlphStatus {
-    fn parse1(&self) -> u32 {
-        //parse for comparing priority
-        match &self {
-            AlphStatus::Right => 3,
-            AlphStatus::PosWrong => 2,
-            AlphStatus::TooMany => 1,
-            AlphStatus::Unknown => 0,
+impl TimeLimits {
+    // the time left before the game or the guess runs out, None without limits
+    fn left(&self, game_start: Instant, guess_start: Instant) -> Option<Duration> {
+        let total = self
+            .total
+            .map(|t| Duration::from_secs(t).saturating_sub(game_start.elapsed()));
+        let per_guess = self
+            .per_guess
+            .map(|t| Duration::from_secs(t).saturating_sub(guess_start.elapsed()));
+        match (total, per_guess) {
+            (Some(t), Some(g)) => Some(t.min(g)),
+            (t, g) => t.or(g),
         }
     }
 
-    fn parse2(&self) -> Color {
-        //parse for getting color code (None,R,G,B,Y)->(0,1,2,3,4)
-        match &self {
-            AlphStatus::Right => Color::Green,
-            AlphStatus::PosWrong => Color::Yellow,
-            AlphStatus::TooMany => Color::Red,
-            AlphStatus::Unknown => Color::Nothing,
-        }
+    fn is_up(&self, game_start: Instant, guess_start: Instant) -> bool {
+        self.left(game_start, guess_start) == Some(Duration::ZERO)
     }
+}
 
-    fn parse3(&self) -> String {
-        //parse for getting status G,Y,R,X
-        match &self {
-            AlphStatus::Right => "G".to_string(),    //Green
-            AlphStatus::PosWrong => "Y".to_string(), //Yellow
-            AlphStatus::TooMany => "R".to_string(),  //Red
-            AlphStatus::Unknown => "X".to_string(),  //Unknown
-        }
+// the live timer of a timed game, a line above the prompt which is redrawn
+// until the guess is read
+struct Clock {
+    stop: Arc<AtomicBool>,
+    handle: thread::JoinHandle<()>,
+}
+
+impl Clock {
+    fn start(limits: TimeLimits, game_start: Instant, guess_start: Instant) -> Clock {
+        let line = move || {
+            let mut line = format!("Time: {}", Wordle::fmt_time(game_start.elapsed()));
+            if let Some(left) = limits.left(game_start, guess_start) {
+                line += &format!(", {:.1}s left", left.as_secs_f32());
+            }
+            console::style(line).bold().blue().to_string()
+        };
+        println!("{}", line());
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        let handle = thread::spawn(move || loop {
+            thread::sleep(Duration::from_millis(100));
+            if stopped.load(Ordering::Relaxed) {
+                break;
+            }
+            // save the cursor, redraw the line above and restore the cursor
+            print!("\x1b7\x1b[1A\r\x1b[2K{}\x1b8", line());
+            io::stdout().flush().unwrap();
+        });
+        Clock { stop, handle }
+    }
+
+    fn stop(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        self.handle.join().unwrap();
     }
 }
 
 struct Wordle {
-    key_word: String,
+    // one key word for every board
+    key_words: Vec<String>,
     hard_mod: bool,
-    stats: bool,
-    seed: u64,
+    ultra_hard: bool,
     tty: bool,
-    final_set: Vec<String>,
     acceptable_set: Vec<String>,
+    recommender: Option<Recommender>,
+    probes: Probes,
+    // None for unlimited guesses
+    max_guesses: Option<u32>,
+    // in evil mode, the final words the answer is not chosen from yet
+    evil: Option<Vec<String>>,
+    // in lying mode, the seed of the letter which lies in every pattern
+    lie_seed: Option<u64>,
+    // in timed mode, the time limits of the game
+    timed: Option<TimeLimits>,
 }
 
 impl Wordle {
     const SEED: u64 = 19260817998244353;
-    const ALPHABET: &'static str = "abcdefghijklmnopqrstuvwxyz";
-
-    fn printall(pln: bool, words: &str, tty: bool, bold: Option<bool>, color: Option<Color>) {
-        if tty {
-            // color: 0->nothing 1->red 2->green 3->blue 4->yellow
-            let bd: bool = bold.unwrap_or(false);
-            let mut col: Color = color.unwrap_or(Color::Nothing);
-            let mut stl = console::style(words.to_string());
-            if bd {
-                stl = stl.bold();
-            }
-            match col {
-                Color::Red => stl = stl.red(),
-                Color::Green => stl = stl.green(),
-                Color::Blue => stl = stl.blue(),
-                Color::Yellow => stl = stl.yellow(),
-                _ => {}
-            };
-            match pln {
-                true => println!("{}", stl),
-                false => print!("{}", stl),
-            };
-            io::stdout().flush().unwrap();
-        }
-    }
+    const MAX_GUESSES: u32 = 6;
+    const MAX_BOARDS: usize = 8;
 
-    fn println(words: &str, tty: bool, bold: Option<bool>, color: Option<Color>) {
-        Wordle::printall(true, words, tty, bold, color);
-    }
-
-    fn print(words: &str, tty: bool, bold: Option<bool>, color: Option<Color>) {
-        Wordle::printall(false, words, tty, bold, color);
+    fn trans_to_onum(cnt: usize) -> String {
+        let suffix = match (cnt % 10, cnt % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+        format!("{}{}", cnt, suffix)
     }
 
-    fn testout(words: &str, tty: bool) {
-        if !tty {
-            print!("{}", words.to_string());
-        }
+    // a duration like "01:02.3"
+    fn fmt_time(time: Duration) -> String {
+        let tenths = time.as_millis() / 100;
+        format!(
+            "{:02}:{:02}.{}",
+            tenths / 600,
+            tenths / 10 % 60,
+            tenths % 10
+        )
     }
 
-    fn read() -> String {
-        let mut key_word = String::new();
-        io::stdin().read_line(&mut key_word);
-        key_word = key_word.trim().to_string();
-        key_word
+    fn unix_millis() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |t| t.as_millis() as u64)
     }
 
-    fn new(
-        key_word: String,
-        hard_mod: bool,
-        stats: bool,
-        seed: u64,
-        tty: bool,
-        final_set: Vec<String>,
-        acceptable_set: Vec<String>,
-    ) -> Wordle {
-        Wordle {
-            key_word: key_word,
-            hard_mod: hard_mod,
-            stats: stats,
-            seed: seed,
-            tty: tty,
-            final_set: final_set,
-            acceptable_set: acceptable_set,
+    // the live timer is only shown on a terminal
+    fn start_clock(&self, game_start: Instant, guess_start: Instant) -> Option<Clock> {
+        match (self.tty, self.timed) {
+            (true, Some(limits)) => Some(Clock::start(limits, game_start, guess_start)),
+            _ => None,
         }
     }
 
-    fn trans_to_onum(cnt: usize) -> String {
-        match cnt {
-            1 => "1st".to_string(),
-            2 => "2nd".to_string(),
-            3 => "3rd".to_string(),
-            4 => "4th".to_string(),
-            5 => "5th".to_string(),
-            6 => "6th".to_string(),
-            _ => "Too Large".to_string(), //it won't occur
-        }
+    // the answers of the boards not solved, like "CRANE SLATE"
+    fn missed(key_words: &[String], solved: &[bool]) -> String {
+        key_words
+            .iter()
+            .zip(solved)
+            .filter(|(_, &s)| !s)
+            .map(|(w, _)| w.to_uppercase())
+            .collect::<Vec<String>>()
+            .join(" ")
     }
 
-    fn check_hard_mod(
-        &self,
-        input_word: &str,
-        curstatus: &Vec<AlphStatus>,
-        status: &HashMap<char, AlphStatus>,
-    ) -> bool {
+    // in hard mode the guess keeps to what every board still unsolved has shown
+    fn check_hard_mod(&self, input_word: &str, boards: &[&Knowledge]) -> Result<(), HardModeErr> {
         if !self.hard_mod {
-            return true;
-        }
-        let input: String = input_word.to_string();
-        let mut tmp: usize = 0;
-        let mut ninput: Vec<char> = vec![];
-        for (&c1, &c2) in self
-            .key_word
-            .chars()
-            .collect::<Vec<char>>()
-            .iter()
-            .zip(input.chars().collect::<Vec<char>>().iter())
-        {
-            if curstatus[tmp] == AlphStatus::Right {
-                if c1 != c2 {
-                    return false;
-                }
-            } else {
-                ninput.push(c2);
-            }
-            tmp += 1;
+            return Ok(());
         }
-        for c in Wordle::ALPHABET.chars() {
-            if *status.get(&c).unwrap() == AlphStatus::PosWrong {
-                if !ninput.contains(&c) {
-                    return false;
-                }
-            }
+        for knowledge in boards {
+            knowledge
+                .constraints
+                .check_hard(input_word, self.ultra_hard)?;
         }
-        true
-    }
-
-    fn check_word(
-        &self,
-        input_word: &str,
-        curstatus: &Vec<AlphStatus>,
-        status: &HashMap<char, AlphStatus>,
-    ) -> bool {
-        input_word.len() == 5
-            && self.acceptable_set.contains(&input_word.to_string())
-            && self.check_hard_mod(input_word, curstatus, status)
+        Ok(())
     }
 
-    fn check_possible(
-        input: &str,
-        status: &HashMap<char, AlphStatus>,
-        green_word: &Vec<char>,
-        numbers: &mut HashMap<char, i32>,
-        forbid: &mut HashMap<char, Vec<u32>>,
-    ) -> bool {
-        let word: String = input.to_string();
-        let mut tmp: usize = 0;
-        let mut ninput: Vec<char> = vec![];
-        let mut cnt_map: HashMap<char, i32> = HashMap::new();
-        for c in word.chars() {
-            *cnt_map.entry(c).or_insert(0) += 1;
-        }
-        for c in word.chars() {
-            if forbid.entry(c).or_insert(vec![]).contains(&(tmp as u32)) {
-                return false;
-            }
-            let cnt = (*numbers).entry(c).or_insert(-1);
-            if *cnt != -1 && *cnt_map.get(&c).unwrap() != *cnt {
-                return false;
-            }
-            if green_word[tmp] != '\0' && c != green_word[tmp] {
-                return false;
-            } else {
-                ninput.push(c);
-            }
-            tmp += 1;
-        }
-        for c in Wordle::ALPHABET.chars() {
-            if *status.get(&c).unwrap() == AlphStatus::PosWrong {
-                if !ninput.contains(&c) {
-                    return false;
-                }
-            }
+    fn check_word(&self, input_word: &str, boards: &[&Knowledge]) -> Result<(), String> {
+        if input_word.len() != self.key_words[0].len()
+            || !self.acceptable_set.contains(&input_word.to_string())
+        {
+            return Err("Key word format error or not in word list.".to_string());
         }
-        true
+        self.check_hard_mod(input_word, boards)
+            .map_err(|e| e.to_string())
     }
 
+    // recommend words for the board, which keep to hard mode on every board
+    // still unsolved
     fn recommend_word(
         &self,
-        status: &HashMap<char, AlphStatus>,
-        green_word: &Vec<char>,
-        numbers: &mut HashMap<char, i32>,
-        forbid: &mut HashMap<char, Vec<u32>>,
+        recommender: &Recommender,
+        knowledge: &Knowledge,
+        unsolved: &[&Knowledge],
     ) {
-        let mut cnt: u32 = 0;
-        let mut possible_word: Vec<String> = vec![];
-        Wordle::println(
-            "Possibly correct words:",
+        let candidates = recommender.candidates(|w| knowledge.check_possible(w));
+        let answers = recommender.possible_answers(&candidates);
+        display::println(
+            &format!("Possible answers ({}):", answers.len()),
             true,
             Some(true),
             Some(Color::Blue),
         );
-        for word in &self.acceptable_set {
-            if cnt == 5 {
-                print!("...");
-                cnt += 1;
-            }
-            if Wordle::check_possible(&word, status, green_word, numbers, forbid) {
-                if cnt < 5 {
-                    print!(
-                        "{}{}",
-                        match cnt {
-                            0 => "",
-                            _ => " ",
-                        },
-                        &word.to_uppercase()
-                    );
-                }
-                possible_word.push(word.to_string());
-                cnt += 1;
-            }
-        }
-        println!("");
-
-        // a slow way to calculate shanon information enrtopy
-        let total: u32 = possible_word.len() as u32;
-        let mut words: HashMap<String, f32> = HashMap::new();
-        for word in &possible_word {
-            let mut cnt: Vec<u32> = vec![0; 243]; //243=3^5 which present all the states
-            for input in &possible_word {
-                if word != input {
-                    let mut map = HashMap::new();
-                    let mut curstatus = vec![0; 5];
-                    let mut tmp: usize = 0;
-                    for (&c1, &c2) in word
-                        .chars()
-                        .collect::<Vec<char>>()
-                        .iter()
-                        .zip(input.chars().collect::<Vec<char>>().iter())
-                    {
-                        let count = map.entry(c1).or_insert(0);
-                        if c1 == c2 {
-                            curstatus[tmp] = 2;
-                        } else {
-                            *count += 1;
-                        }
-                        tmp += 1;
-                    }
-                    tmp = 0;
-                    for c in input.chars() {
-                        let count = map.entry(c).or_insert(0);
-                        if *count > 0 && curstatus[tmp] != 2 {
-                            curstatus[tmp] = 1;
-                            *count -= 1;
-                        }
-                        tmp += 1;
-                    }
-                    let mut st: u32 = 0;
-                    let mut base: u32 = 1;
-                    for i in 0..5 {
-                        st += base * curstatus[i];
-                        base = base * 3;
-                    }
-                    cnt[st as usize] += 1;
-                }
-            }
-            let mut ans: f32 = 0.0;
-            for i in 0..243 {
-                if cnt[i] != 0 {
-                    ans -= (cnt[i] as f32) / (total as f32)
-                        * ((cnt[i] as f32) / (total as f32)).log2();
-                }
-            }
-            words.insert(word.to_string(), ans);
+        println!("{}", display::preview(&answers, 5));
+        let others = recommender.other_words(|w| knowledge.check_possible(w));
+        if !others.is_empty() {
+            display::println(
+                &format!("Other acceptable words ({}):", others.len()),
+                true,
+                Some(true),
+                Some(Color::Blue),
+            );
+            println!("{}", display::preview(&others, 5));
         }
-        let mut count_vec: Vec<(&String, &f32)> = words.iter().collect();
-        count_vec.sort_by(|a, b| a.0.cmp(b.0));
-        count_vec.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap_or(std::cmp::Ordering::Equal));
-        Wordle::println("I recommend you use:", true, Some(true), Some(Color::Blue));
+
+        let constraints: Vec<&Constraints> = unsolved.iter().map(|k| &k.constraints).collect();
+        let hard_mode = match self.hard_mod {
+            true => Some((constraints.as_slice(), self.ultra_hard)),
+            false => None,
+        };
+        let probes = recommender.probes(&candidates, self.probes, hard_mode);
+        let count_vec = recommender.recommend_words(&candidates, &probes);
+        display::println("I recommend you use:", true, Some(true), Some(Color::Blue));
         for (index, value) in count_vec.iter().enumerate() {
             if index > 4 {
                 break;
@@ -387,172 +246,287 @@ impl Wordle {
                 value.1
             );
         }
-        println!("");
+        println!();
     }
 
-    fn play(&self, words_map: &mut HashMap<String, u32>) -> (u32, u32, Game) {
+    // play the game on from its guesses so far, saving it after every guess
+    fn play(
+        &self,
+        words_map: &mut HashMap<String, u32>,
+        mut game: Game,
+        save: SaveRound,
+    ) -> Result<(u32, u32, Game), Box<dyn std::error::Error>> {
         let mut cnt: usize = 0;
         let mut win_tag: u32 = 0;
-        let mut status = HashMap::new();
-        for c in Wordle::ALPHABET.chars() {
-            status.insert(c, AlphStatus::Unknown);
+        let boards = self.key_words.len();
+        let mut knowledge: Vec<Knowledge> = match self.lie_seed {
+            Some(_) => vec![Knowledge::lying(); boards],
+            None => vec![Knowledge::new(); boards],
+        };
+        let mut liar = self.lie_seed.map(SplitMix64::new);
+        // whether every board has been solved
+        let mut solved: Vec<bool> = vec![false; boards];
+        let mut key_words = self.key_words.clone();
+        let mut evil_left = self.evil.clone();
+        // the guesses of a resumed game are played again first
+        let mut replay: VecDeque<String> =
+            game.guesses.drain(..).map(|g| g.to_lowercase()).collect();
+        game.answer = key_words[0].to_uppercase();
+        if boards > 1 {
+            game.answers = key_words.iter().map(|w| w.to_uppercase()).collect();
         }
-        let mut curstatus: Vec<AlphStatus> = vec![AlphStatus::TooMany; 5];
-        let mut green_word: Vec<char> = vec!['\0'; 5];
-        let mut numbers: HashMap<char, i32> = HashMap::new();
-        let mut forbid: HashMap<char, Vec<u32>> = HashMap::new();
-        let mut game = Game::new();
-        game.answer = self.key_word.to_string().to_uppercase();
+        // a resumed game goes on from the time it has taken
+        let game_start = Instant::now()
+            .checked_sub(Duration::from_millis(game.duration.unwrap_or(0)))
+            .unwrap_or_else(Instant::now);
 
         loop {
-            cnt = cnt + 1;
-            let mut input_word = String::new();
-            input_word = "tares".to_string();
-            if self.tty && cnt != 1 {
-                self.recommend_word(&status, &green_word, &mut numbers, &mut forbid);
+            cnt += 1;
+            if let Some(recommender) = &self.recommender {
+                if cnt != 1 && replay.is_empty() {
+                    // help with the unsolved board closest to be solved
+                    let board = (0..boards)
+                        .filter(|&b| !solved[b])
+                        .min_by_key(|&b| {
+                            recommender
+                                .candidates(|w| knowledge[b].check_possible(w))
+                                .len()
+                        })
+                        .unwrap_or(0);
+                    if boards > 1 {
+                        display::println(
+                            &format!("Board {}:", board + 1),
+                            true,
+                            Some(true),
+                            Some(Color::Blue),
+                        );
+                    }
+                    let unsolved: Vec<&Knowledge> = (0..boards)
+                        .filter(|&b| !solved[b])
+                        .map(|b| &knowledge[b])
+                        .collect();
+                    self.recommend_word(recommender, &knowledge[board], &unsolved);
+                }
             }
-            Wordle::print(
-                &format!("Start Guessing({}): ", Wordle::trans_to_onum(cnt)).to_string(),
+            let replaying = !replay.is_empty();
+            let guess_start = Instant::now();
+            let mut clock = match replaying {
+                true => None,
+                false => self.start_clock(game_start, guess_start),
+            };
+            display::print(
+                &format!("Start Guessing({}): ", Wordle::trans_to_onum(cnt)),
                 self.tty,
                 Some(true),
                 Some(Color::Blue),
             );
-            loop {
-                input_word = Wordle::read();
-                if self.check_word(&input_word, &curstatus, &status) {
-                    break;
-                } else {
-                    Wordle::print(
-                        "Key word format error or not in word list. Input again: ",
+            if let Some(input_word) = replay.front() {
+                display::println(&input_word.to_uppercase(), self.tty, None, None);
+            }
+            let input_word = loop {
+                if let Some(input_word) = replay.pop_front() {
+                    break Some(input_word);
+                }
+                // a game with time limits is lost as soon as the time is up
+                let input_word = match self
+                    .timed
+                    .and_then(|limits| limits.left(game_start, guess_start))
+                {
+                    Some(left) => display::read_timeout(left),
+                    None => Some(display::read()),
+                };
+                if let Some(clock) = clock.take() {
+                    clock.stop();
+                }
+                let input_word = match input_word {
+                    Some(input_word) => input_word,
+                    None => {
+                        // the prompt is still waiting for the guess
+                        display::println("", self.tty, None, None);
+                        break None;
+                    }
+                };
+                if self
+                    .timed
+                    .is_some_and(|limits| limits.is_up(game_start, guess_start))
+                {
+                    break None;
+                }
+                let unsolved: Vec<&Knowledge> = (0..boards)
+                    .filter(|&b| !solved[b])
+                    .map(|b| &knowledge[b])
+                    .collect();
+                if let Err(message) = self.check_word(&input_word, &unsolved) {
+                    clock = self.start_clock(game_start, guess_start);
+                    display::print(
+                        &format!("{} Input again: ", message),
                         self.tty,
                         Some(false),
                         Some(Color::Red),
                     );
-                    Wordle::testout("INVALID\n", self.tty);
+                    display::testout("INVALID\n", self.tty);
+                } else {
+                    break Some(input_word);
                 }
-            }
+            };
+            let input_word = match input_word {
+                Some(input_word) => input_word,
+                None => {
+                    display::println("LOST, time is up.", self.tty, Some(true), Some(Color::Red));
+                    display::testout(
+                        &format!("FAILED {}\n", Wordle::missed(&key_words, &solved)),
+                        self.tty,
+                    );
+                    cnt = 0;
+                    break;
+                }
+            };
 
             game.guesses.push(input_word.to_string().to_uppercase());
+            if self.timed.is_some() && !replaying {
+                game.timestamps.push(Wordle::unix_millis());
+            }
             *words_map.entry(input_word.to_string()).or_insert(0) += 1;
 
-            //update status of the word
-            let mut map = HashMap::new();
-            let mut cnt_map = HashMap::new();
-            curstatus = vec![AlphStatus::TooMany; 5];
-            let mut tmp: usize = 0;
-            for (&c1, &c2) in self
-                .key_word
-                .chars()
-                .collect::<Vec<char>>()
-                .iter()
-                .zip(input_word.chars().collect::<Vec<char>>().iter())
-            {
-                let count = map.entry(c1).or_insert(0);
-                if c1 == c2 {
-                    curstatus[tmp] = AlphStatus::Right;
-                    *cnt_map.entry(c2).or_insert(0) += 1;
-                    green_word[tmp] = c1.clone();
-                } else {
-                    *count += 1;
-                }
-                tmp += 1;
-            }
-            tmp = 0;
-            for c in input_word.chars() {
-                let count = map.entry(c).or_insert(0);
-                if *count > 0 && curstatus[tmp] != AlphStatus::Right {
-                    curstatus[tmp] = AlphStatus::PosWrong;
-                    *cnt_map.entry(c).or_insert(0) += 1;
-                    (*forbid.entry(c).or_insert(vec![])).push(tmp as u32);
-                    *count -= 1;
-                }
-                if curstatus[tmp] == AlphStatus::TooMany {
-                    *numbers.entry(c).or_insert(-1) = *cnt_map.entry(c).or_insert(0);
-                }
-                tmp += 1;
+            // in evil mode the guess gets the pattern which leaves the most words,
+            // and any of them stands for the answer until the game is over
+            if let Some(left) = &mut evil_left {
+                let (_, bucket) = recommend::partition(&input_word, left)
+                    .into_iter()
+                    .max_by_key(|(pattern, bucket)| (bucket.len(), std::cmp::Reverse(*pattern)))
+                    .unwrap();
+                key_words[0] = bucket[0].to_string();
+                *left = bucket;
             }
 
-            //update stauts of the alphabet
-            tmp = 0;
-            for c in input_word.chars() {
-                let oldstatus: &AlphStatus = status.get(&c).unwrap();
-                let newstatus: &AlphStatus = &curstatus[tmp];
-                if oldstatus.parse1() < newstatus.parse1() {
-                    status.insert(c, *newstatus);
+            //update status of the word and the alphabet on every board
+            let mut curstatus: Vec<Vec<AlphStatus>> = vec![];
+            for (b, key_word) in key_words.iter().enumerate() {
+                let mut pattern = score(&input_word, key_word);
+                if let Some(rng) = &mut liar {
+                    if !pattern.is_win() {
+                        pattern = pattern.lie(rng);
+                    }
                 }
-                tmp += 1;
+                curstatus.push(match solved[b] {
+                    true => pattern.to_status(),
+                    false => knowledge[b].update(&input_word, pattern),
+                });
+            }
+            // the keyboard shows the best status a letter has on any board
+            let mut alphabet: HashMap<char, AlphStatus> = HashMap::new();
+            for c in ALPHABET.chars() {
+                let status = knowledge
+                    .iter()
+                    .map(|k| *k.status.get(&c).unwrap())
+                    .max_by_key(|s| s.parse1())
+                    .unwrap();
+                alphabet.insert(c, status);
             }
 
-            // println!("{:?}", curstatus);
-
-            // print status for user
-            tmp = 0;
-            for c in input_word.chars() {
-                Wordle::print(
-                    &c.to_string(),
-                    self.tty,
-                    Some(false),
-                    Some(curstatus[tmp].parse2()),
-                );
-                tmp += 1;
+            // print status for user, a board solved before shows nothing
+            for (b, status) in curstatus.iter().enumerate() {
+                if b > 0 {
+                    display::print("  ", self.tty, None, None);
+                }
+                for (tmp, c) in input_word.chars().enumerate() {
+                    match solved[b] {
+                        true => display::print(" ", self.tty, None, None),
+                        false => display::print(
+                            &c.to_string(),
+                            self.tty,
+                            Some(false),
+                            Some(status[tmp].parse2()),
+                        ),
+                    }
+                }
             }
-            Wordle::println("", self.tty, None, None);
-            for c in Wordle::ALPHABET.chars() {
-                Wordle::print(
+            display::println("", self.tty, None, None);
+            for c in ALPHABET.chars() {
+                display::print(
                     &c.to_string(),
                     self.tty,
                     Some(false),
-                    Some(status.get(&c).unwrap().parse2()),
+                    Some(alphabet.get(&c).unwrap().parse2()),
                 );
             }
-            Wordle::println("", self.tty, None, None);
+            display::println("", self.tty, None, None);
 
             // print status for test
-            for i in 0..5 {
-                Wordle::testout(&curstatus[i].parse3(), self.tty);
+            for status in &curstatus {
+                for s in status {
+                    display::testout(&s.parse3(), self.tty);
+                }
+                display::testout(" ", self.tty);
             }
-            Wordle::testout(" ", self.tty);
-            for c in Wordle::ALPHABET.chars() {
-                Wordle::testout(&status.get(&c).unwrap().parse3(), self.tty);
+            for c in ALPHABET.chars() {
+                display::testout(&alphabet.get(&c).unwrap().parse3(), self.tty);
             }
-            Wordle::testout("\n", self.tty);
+            display::testout("\n", self.tty);
 
             // judement
-            if input_word == self.key_word {
-                Wordle::println(
-                    &format!("CORRECT, guess time: {}", cnt).to_string(),
+            for (b, key_word) in key_words.iter().enumerate() {
+                if !solved[b] && input_word == *key_word {
+                    solved[b] = true;
+                    if boards > 1 {
+                        display::println(
+                            &format!("Board {} solved in {} guesses.", b + 1, cnt),
+                            self.tty,
+                            Some(true),
+                            Some(Color::Green),
+                        );
+                    }
+                }
+            }
+            if solved.iter().all(|&s| s) {
+                display::println(
+                    &format!("CORRECT, guess time: {}", cnt),
                     self.tty,
                     Some(true),
                     Some(Color::Green),
                 );
-                Wordle::testout(&format!("CORRECT {}\n", cnt).to_string(), self.tty);
+                display::testout(&format!("CORRECT {}\n", cnt), self.tty);
                 win_tag = 1;
                 break;
             }
-            if cnt == 6 {
-                Wordle::println(
+            // the guesses of a resumed game are never cut
+            if replay.is_empty() && self.max_guesses.is_some_and(|max| cnt as u32 >= max) {
+                display::println(
                     "LOST, you failed too many times.",
                     self.tty,
                     Some(true),
                     Some(Color::Red),
                 );
-                Wordle::testout(
-                    &format!("FAILED {}\n", &self.key_word.to_uppercase()).to_string(),
+                display::testout(
+                    &format!("FAILED {}\n", Wordle::missed(&key_words, &solved)),
                     self.tty,
                 );
                 cnt = 0;
                 break;
             }
+
+            // keep the round so far, so it can be resumed
+            if self.timed.is_some() {
+                game.duration = Some(game_start.elapsed().as_millis() as u64);
+            }
+            if !replaying {
+                save(&game)?;
+            }
         }
-        (win_tag, cnt as u32, game)
+        game.answer = key_words[0].to_uppercase();
+        if self.timed.is_some() {
+            game.duration = Some(game_start.elapsed().as_millis() as u64);
+        }
+        Ok((win_tag, cnt as u32, game))
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Deserialize, Default)]
 #[serde(default)]
 struct Config {
     random: Option<bool>,
     difficult: Option<bool>,
+    ultra_difficult: Option<bool>,
     stats: Option<bool>,
     day: Option<u32>,
     seed: Option<u64>,
@@ -560,6 +534,26 @@ struct Config {
     acceptable_set: Option<String>,
     state: Option<String>,
     word: Option<String>,
+    cache_dir: Option<String>,
+    probes: Option<String>,
+    weights: Option<String>,
+    length: Option<usize>,
+    // 0 for unlimited guesses
+    max_guesses: Option<u32>,
+    boards: Option<usize>,
+    evil: Option<bool>,
+    lie: Option<bool>,
+    timed: Option<bool>,
+    // in seconds
+    time_limit: Option<u64>,
+    guess_time_limit: Option<u64>,
+    // like "2022-01-31"
+    date: Option<String>,
+    today: Option<bool>,
+    epoch: Option<String>,
+    repair: Option<bool>,
+    no_state: Option<bool>,
+    player: Option<String>,
 }
 
 impl Config {
@@ -567,6 +561,7 @@ impl Config {
         Config {
             random: None,
             difficult: None,
+            ultra_difficult: None,
             stats: None,
             day: None,
             seed: None,
@@ -574,6 +569,23 @@ impl Config {
             acceptable_set: None,
             state: None,
             word: None,
+            cache_dir: None,
+            probes: None,
+            weights: None,
+            length: None,
+            max_guesses: None,
+            boards: None,
+            evil: None,
+            lie: None,
+            timed: None,
+            time_limit: None,
+            guess_time_limit: None,
+            date: None,
+            today: None,
+            epoch: None,
+            repair: None,
+            no_state: None,
+            player: None,
         }
     }
 }
@@ -594,19 +606,61 @@ impl CliApp {
             "hard_mod" => {
                 self.cli_args.is_present(arg)
                     | (self.config.difficult.is_some() && self.config.difficult.unwrap())
+                    | self.is_present("ultra_hard")
+            }
+            "ultra_hard" => {
+                self.cli_args.is_present(arg)
+                    | (self.config.ultra_difficult.is_some()
+                        && self.config.ultra_difficult.unwrap())
             }
             "stats" => {
                 self.cli_args.is_present(arg)
                     | (self.config.stats.is_some() && self.config.stats.unwrap())
             }
-            "day" => self.cli_args.is_present(arg) | (self.config.day != None),
-            "seed" => self.cli_args.is_present(arg) | (self.config.seed != None),
-            "key_word" => self.cli_args.is_present(arg) | (self.config.word != None),
-            "final_set_file" => self.cli_args.is_present(arg) | (self.config.final_set != None),
+            "day" => self.cli_args.is_present(arg) | self.config.day.is_some(),
+            "seed" => self.cli_args.is_present(arg) | self.config.seed.is_some(),
+            "key_word" => self.cli_args.is_present(arg) | self.config.word.is_some(),
+            "final_set_file" => self.cli_args.is_present(arg) | self.config.final_set.is_some(),
             "acceptable_set_file" => {
-                self.cli_args.is_present(arg) | (self.config.acceptable_set != None)
+                self.cli_args.is_present(arg) | self.config.acceptable_set.is_some()
+            }
+            "state_file" => self.cli_args.is_present(arg) | self.config.state.is_some(),
+            "cache_dir" => self.cli_args.is_present(arg) | self.config.cache_dir.is_some(),
+            "probes" => self.cli_args.is_present(arg) | self.config.probes.is_some(),
+            "weights_file" => self.cli_args.is_present(arg) | self.config.weights.is_some(),
+            "length" => self.cli_args.is_present(arg) | self.config.length.is_some(),
+            "max_guesses" => self.cli_args.is_present(arg) | self.config.max_guesses.is_some(),
+            "boards" => self.cli_args.is_present(arg) | self.config.boards.is_some(),
+            "lie" => {
+                self.cli_args.is_present(arg)
+                    | (self.config.lie.is_some() && self.config.lie.unwrap())
+            }
+            "evil" => {
+                self.cli_args.is_present(arg)
+                    | (self.config.evil.is_some() && self.config.evil.unwrap())
+            }
+            "timed" => {
+                self.cli_args.is_present(arg)
+                    | (self.config.timed.is_some() && self.config.timed.unwrap())
+            }
+            "time_limit" => self.cli_args.is_present(arg) | self.config.time_limit.is_some(),
+            "guess_time_limit" => {
+                self.cli_args.is_present(arg) | self.config.guess_time_limit.is_some()
+            }
+            "date" => self.cli_args.is_present(arg) | self.config.date.is_some(),
+            "today" => {
+                self.cli_args.is_present(arg)
+                    | (self.config.today.is_some() && self.config.today.unwrap())
+            }
+            "epoch" => self.cli_args.is_present(arg) | self.config.epoch.is_some(),
+            "no_state" => {
+                self.cli_args.is_present(arg)
+                    | (self.config.no_state.is_some() && self.config.no_state.unwrap())
+            }
+            "repair" => {
+                self.cli_args.is_present(arg)
+                    | (self.config.repair.is_some() && self.config.repair.unwrap())
             }
-            "state_file" => self.cli_args.is_present(arg) | (self.config.state != None),
             _ => false,
         }
     }
@@ -653,8 +707,73 @@ impl CliApp {
                     }
                 }
             },
+            "cache_dir" => match &self.config.cache_dir {
+                None => self.cli_args.value_of(arg),
+                Some(s) => {
+                    if self.cli_args.value_of(arg).is_some() {
+                        self.cli_args.value_of(arg)
+                    } else {
+                        Some(s.as_str())
+                    }
+                }
+            },
+            "probes" => match &self.config.probes {
+                None => self.cli_args.value_of(arg),
+                Some(s) => {
+                    if self.cli_args.value_of(arg).is_some() {
+                        self.cli_args.value_of(arg)
+                    } else {
+                        Some(s.as_str())
+                    }
+                }
+            },
+            "weights_file" => match &self.config.weights {
+                None => self.cli_args.value_of(arg),
+                Some(s) => {
+                    if self.cli_args.value_of(arg).is_some() {
+                        self.cli_args.value_of(arg)
+                    } else {
+                        Some(s.as_str())
+                    }
+                }
+            },
+            "date" => match &self.config.date {
+                None => self.cli_args.value_of(arg),
+                Some(s) => {
+                    if self.cli_args.value_of(arg).is_some() {
+                        self.cli_args.value_of(arg)
+                    } else {
+                        Some(s.as_str())
+                    }
+                }
+            },
+            "player" => match &self.config.player {
+                None => self.cli_args.value_of(arg),
+                Some(s) => {
+                    if self.cli_args.value_of(arg).is_some() {
+                        self.cli_args.value_of(arg)
+                    } else {
+                        Some(s.as_str())
+                    }
+                }
+            },
+            "epoch" => match &self.config.epoch {
+                None => self.cli_args.value_of(arg),
+                Some(s) => {
+                    if self.cli_args.value_of(arg).is_some() {
+                        self.cli_args.value_of(arg)
+                    } else {
+                        Some(s.as_str())
+                    }
+                }
+            },
             "day" => self.cli_args.value_of(arg),
             "seed" => self.cli_args.value_of(arg),
+            "length" => self.cli_args.value_of(arg),
+            "max_guesses" => self.cli_args.value_of(arg),
+            "boards" => self.cli_args.value_of(arg),
+            "time_limit" => self.cli_args.value_of(arg),
+            "guess_time_limit" => self.cli_args.value_of(arg),
             _ => Some(""),
         }
     }
@@ -686,6 +805,13 @@ impl CliApp {
                         .takes_value(false)
                         .help("Toggle to turn on difficult mode."),
                 )
+                .arg(
+                    Arg::with_name("ultra_hard")
+                        .short('U')
+                        .long("ultra-difficult")
+                        .takes_value(false)
+                        .help("Toggle to turn on ultra difficult mode, which also forbids known absent letters and known wrong positions."),
+                )
                 .arg(
                     Arg::with_name("stats")
                         .short('t')
@@ -700,12 +826,30 @@ impl CliApp {
                         .takes_value(true)
                         .help("The day that you wanna start your game."),
                 )
+                .arg(
+                    Arg::with_name("date")
+                        .long("date")
+                        .takes_value(true)
+                        .help("The date of the daily puzzle to play, like 2022-01-31. Another round plays the next date not played, up to today."),
+                )
+                .arg(
+                    Arg::with_name("today")
+                        .long("today")
+                        .takes_value(false)
+                        .help("Toggle to play the daily puzzle of today (UTC)."),
+                )
+                .arg(
+                    Arg::with_name("epoch")
+                        .long("epoch")
+                        .takes_value(true)
+                        .help("The date of the first daily puzzle, 2021-06-19 by default."),
+                )
                 .arg(
                     Arg::with_name("seed")
                         .short('s')
                         .long("seed")
                         .takes_value(true)
-                        .help("The random seed for generating a key word."),
+                        .help("The random seed for generating a key word, and for the lies in lying mode."),
                 )
                 .arg(
                     Arg::with_name("final_set_file")
@@ -726,7 +870,94 @@ impl CliApp {
                         .short('S')
                         .long("state")
                         .takes_value(true)
-                        .help("The game state file to load previous games."),
+                        .global(true)
+                        .help("The game state file to load previous games, created if it's missing. On a terminal it's state.json in $XDG_DATA_HOME/wordle (or ~/.local/share/wordle) by default."),
+                )
+                .arg(
+                    Arg::with_name("player")
+                        .long("player")
+                        .takes_value(true)
+                        .global(true)
+                        .help("The name of the player, whose games and stats are kept apart from the others' in the state file."),
+                )
+                .arg(
+                    Arg::with_name("no_state")
+                        .long("no-state")
+                        .takes_value(false)
+                        .help("Toggle to keep no state file, even on a terminal."),
+                )
+                .arg(
+                    Arg::with_name("repair")
+                        .long("repair")
+                        .takes_value(false)
+                        .help("Toggle to fix the total rounds of the state file when they don't match its games, instead of stopping."),
+                )
+                .arg(
+                    Arg::with_name("cache_dir")
+                        .long("cache-dir")
+                        .takes_value(true)
+                        .help("The directory to cache the pattern table of the word sets."),
+                )
+                .arg(
+                    Arg::with_name("probes")
+                        .long("probes")
+                        .takes_value(true)
+                        .possible_values(["any", "candidates"])
+                        .help("Recommend any acceptable word (default) or only the possible key words."),
+                )
+                .arg(
+                    Arg::with_name("weights_file")
+                        .long("weights")
+                        .takes_value(true)
+                        .help("The file of the weight of every final word, one \"word weight\" per line."),
+                )
+                .arg(
+                    Arg::with_name("length")
+                        .long("length")
+                        .takes_value(true)
+                        .help("The number of letters of the words, from 4 to 11. Lengths other than 5 need both word set files."),
+                )
+                .arg(
+                    Arg::with_name("max_guesses")
+                        .long("max-guesses")
+                        .takes_value(true)
+                        .help("The number of guesses of a game or 'unlimited', 6 by default and one more for every extra board."),
+                )
+                .arg(
+                    Arg::with_name("boards")
+                        .long("boards")
+                        .takes_value(true)
+                        .help("The number of boards played at once, from 1 to 8, each board adds a guess. Give one key word for every board to -w/--word, like \"crane,slate\"."),
+                )
+                .arg(
+                    Arg::with_name("evil")
+                        .long("evil")
+                        .takes_value(false)
+                        .help("Toggle to turn on evil mode, where the game keeps the answer open as long as it can, with unlimited guesses by default."),
+                )
+                .arg(
+                    Arg::with_name("lie")
+                        .long("lie")
+                        .takes_value(false)
+                        .help("Toggle to turn on lying mode, where exactly one letter of every pattern has a wrong color, picked by -s/--seed."),
+                )
+                .arg(
+                    Arg::with_name("timed")
+                        .long("timed")
+                        .takes_value(false)
+                        .help("Toggle to turn on timed mode, which shows a live timer and records the time of every guess."),
+                )
+                .arg(
+                    Arg::with_name("time_limit")
+                        .long("time-limit")
+                        .takes_value(true)
+                        .help("The seconds a timed game can take at most."),
+                )
+                .arg(
+                    Arg::with_name("guess_time_limit")
+                        .long("guess-time-limit")
+                        .takes_value(true)
+                        .help("The seconds a guess of a timed game can take at most."),
                 )
                 .arg(
                     Arg::with_name("config")
@@ -735,16 +966,56 @@ impl CliApp {
                         .takes_value(true)
                         .help("The config file of input args."),
                 )
+                .subcommand(
+                    App::new("leaderboard")
+                        .about("Rank the players of the state file by win rate, average guesses and streak."),
+                )
+                .subcommand(
+                    App::new("export")
+                        .about("Write the games of the state file as a table, of every player or the one of --player.")
+                        .arg(
+                            Arg::with_name("format")
+                                .long("format")
+                                .takes_value(true)
+                                .possible_values(["csv", "jsonl", "markdown"])
+                                .default_value("csv")
+                                .help("The format of the table."),
+                        )
+                        .arg(
+                            Arg::with_name("rows")
+                                .long("rows")
+                                .takes_value(true)
+                                .possible_values(["games", "guesses"])
+                                .default_value("games")
+                                .help("A row for every game, or for every guess with its patterns."),
+                        )
+                        .arg(
+                            Arg::with_name("from")
+                                .long("from")
+                                .takes_value(true)
+                                .help("The first day of the games, like 2022-01-31. A game's day is the date of its puzzle, or the day it was over (UTC)."),
+                        )
+                        .arg(
+                            Arg::with_name("to")
+                                .long("to")
+                                .takes_value(true)
+                                .help("The last day of the games, like 2022-01-31."),
+                        )
+                        .arg(
+                            Arg::with_name("output")
+                                .short('o')
+                                .long("output")
+                                .takes_value(true)
+                                .help("The file to write, the standard output by default."),
+                        ),
+                )
                 .get_matches(),
             config: Config::new(),
         }
     }
 }
 
-fn lines_from_file(filename: impl AsRef<Path>) -> io::Result<Vec<String>> {
-    BufReader::new(File::open(filename)?).lines().collect()
-}
-
+#[allow(clippy::too_many_arguments)]
 fn game_day(
     matches: CliApp,
     first_tag: bool,
@@ -754,24 +1025,31 @@ fn game_day(
     mut try_times: u32,
     mut words: HashMap<String, u32>,
     mut state: State,
-    mut state_file_path: &str,
+    state_file_path: &str,
+    player: Option<String>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let mut final_set: Vec<String> = builtin_words::FINAL.iter().map(|s| s.to_string()).collect();
-    let mut acceptable_set: Vec<String> = builtin_words::ACCEPTABLE
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-    let mut key_word: String = String::new();
+    let mut final_set: Vec<String> = wordlist::builtin_final();
+    let mut acceptable_set: Vec<String> = wordlist::builtin_acceptable();
     let mut seed: u64 = Wordle::SEED;
     let mut hard_mod: bool = false;
+    let mut ultra_hard: bool = false;
     let mut stats: bool = false;
     let tty: bool = atty::is(atty::Stream::Stdout);
 
-    // arg hard_mod --difficult
+    // arg hard_mod --difficult, ultra_hard --ultra-difficult
     if matches.is_present("hard_mod") {
         hard_mod = true;
+        ultra_hard = matches.is_present("ultra_hard");
         if first_tag {
-            Wordle::println("Difficult mode: on", tty, Some(true), Some(Color::Red));
+            display::println(
+                match ultra_hard {
+                    true => "Ultra difficult mode: on",
+                    false => "Difficult mode: on",
+                },
+                tty,
+                Some(true),
+                Some(Color::Red),
+            );
         }
     }
 
@@ -779,7 +1057,7 @@ fn game_day(
     if matches.is_present("stats") {
         stats = true;
         if first_tag {
-            Wordle::println(
+            display::println(
                 "Stats recording mode: on",
                 tty,
                 Some(true),
@@ -788,25 +1066,47 @@ fn game_day(
         }
     }
 
+    // arg length --length
+    let mut length: usize = Pattern::DEFAULT_LEN;
+    if matches.is_present("length") {
+        match matches.value_of("length") {
+            None => {
+                if let Some(len) = matches.config.length {
+                    length = len;
+                }
+            }
+            Some(l) => match l.parse::<usize>() {
+                Ok(len) => length = len,
+                Err(_) => Err(ArgsErr("The word length must be a number."))?,
+            },
+        }
+        if !(Pattern::MIN_LEN..=Pattern::MAX_LEN).contains(&length) {
+            Err(ArgsErr("The word length must be from 4 to 11."))?;
+        }
+        if length != Pattern::DEFAULT_LEN
+            && !(matches.is_present("final_set_file") && matches.is_present("acceptable_set_file"))
+        {
+            Err(ArgsErr(
+                "The built-in word sets only have 5-letter words, give both -f/--final-set and -a/--acceptable-set for other lengths.",
+            ))?;
+        }
+    }
+
     // arg acceptable_set_file --acceptable-set
     if matches.is_present("acceptable_set_file") {
         match matches.value_of("acceptable_set_file") {
             None => return Err(ArgsErr("No input file of acceptable set found."))?,
             Some(pwd) => match pwd.parse::<String>() {
-                Ok(path) => match lines_from_file(path) {
-                    Ok(lines) => acceptable_set = lines,
+                Ok(path) => match wordlist::load_words(path) {
+                    Ok(words) => acceptable_set = words,
                     Err(_) => return Err(ArgsErr("Could not load acceptable set."))?,
                 },
                 Err(_) => return Err(ArgsErr("File path has a wrong format."))?,
             },
         };
-        acceptable_set = acceptable_set.iter().map(|s| s.to_lowercase()).collect();
-        acceptable_set.sort_unstable();
-        acceptable_set.dedup();
-
         for word in &acceptable_set {
-            if word.len() != 5 {
-                return Err(ArgsErr("The acceptable words set has incorrect word."))?;
+            if word.len() != length {
+                Err(ArgsErr("The acceptable words set has incorrect word."))?;
             }
         }
     }
@@ -816,76 +1116,292 @@ fn game_day(
         match matches.value_of("final_set_file") {
             None => return Err(ArgsErr("No input file of final set found."))?,
             Some(pwd) => match pwd.parse::<String>() {
-                Ok(path) => match lines_from_file(path) {
-                    Ok(lines) => final_set = lines,
+                Ok(path) => match wordlist::load_words(path) {
+                    Ok(words) => final_set = words,
                     Err(_) => return Err(ArgsErr("Could not load final set."))?,
                 },
                 Err(_) => return Err(ArgsErr("File path has a wrong format."))?,
             },
         };
-        final_set = final_set.iter().map(|s| s.to_lowercase()).collect();
-        final_set.sort_unstable();
-        final_set.dedup();
-
         for word in &final_set {
-            if word.len() != 5 {
-                return Err(ArgsErr("The final words set has incorrect word."))?;
+            if word.len() != length {
+                Err(ArgsErr("The final words set has incorrect word."))?;
             }
         }
         let acc_set: HashSet<_> = acceptable_set.iter().cloned().collect();
         if !final_set.iter().all(|word| acc_set.contains(word)) {
-            return Err(ArgsErr(
+            Err(ArgsErr(
                 "Every word in the final set should be covered in the acceptable set.",
             ))?;
         }
     }
 
     // handle args confict
-    if (matches.is_present("seed") || matches.is_present("day")) && !matches.is_present("rand_mod")
+    let mut lie = matches.is_present("lie");
+    if matches.is_present("day") && !matches.is_present("rand_mod") {
+        Err(ArgsErr("-d/--day can only be used in random mode."))?;
+    }
+    let date_mod = matches.is_present("date") || matches.is_present("today");
+    if matches.is_present("seed") && !matches.is_present("rand_mod") && !date_mod && !lie {
+        Err(ArgsErr(
+            "-s/--seed can only be used in random mode, date mode or lying mode.",
+        ))?;
+    }
+    if date_mod
+        && (matches.is_present("rand_mod")
+            || matches.is_present("key_word")
+            || matches.is_present("day")
+            || matches.is_present("evil"))
     {
-        return Err(ArgsErr(
-            "-s/--seed and -d/--day can only be used in random mode.",
+        Err(ArgsErr(
+            "Date mode can't be played with random mode, key word mode, -d/--day or evil mode.",
+        ))?;
+    }
+    if matches.is_present("epoch") && !date_mod {
+        Err(ArgsErr("--epoch can only be used in date mode."))?;
+    }
+    if lie && (hard_mod || matches.is_present("evil")) {
+        Err(ArgsErr(
+            "Lying mode can't be played in difficult mode or evil mode.",
         ))?;
     }
 
-    // arg: rand_mod --random
-    if matches.is_present("rand_mod") {
-        if matches.is_present("key_word") {
-            return Err(ArgsErr("Random mode and key word input mode are conflict."))?;
+    // arg seed --seed
+    match matches.value_of("seed") {
+        None => {
+            if let Some(se) = matches.config.seed {
+                seed = se;
+            }
         }
+        Some(s) => match s.parse::<u64>() {
+            Ok(se) => seed = se,
+            Err(_) => return Err(ArgsErr("Your random seed must be a number of type <u64>."))?,
+        },
+    }
+
+    // arg date --date, today --today, epoch --epoch
+    // the puzzle of a date is the one of the day counted from the epoch
+    let mut day = day;
+    let mut date: Option<Date> = None;
+    let mut epoch: Option<Date> = None;
+    if date_mod {
+        let e = match matches.value_of("epoch").map(|e| e.parse::<Date>()) {
+            None => Date::WORDLE_EPOCH,
+            Some(Ok(e)) => e,
+            Some(Err(_)) => Err(ArgsErr("The arg 'epoch' must be a date like 2022-01-31."))?,
+        };
         if first_tag {
-            Wordle::println("Random key word mode", tty, Some(true), Some(Color::Red));
+            let start = match matches.value_of("date").map(|d| d.parse::<Date>()) {
+                None => Date::today(),
+                Some(Ok(d)) if !matches.is_present("today") => d,
+                Some(Ok(_)) => Err(ArgsErr("--date and --today are conflict."))?,
+                Some(Err(_)) => Err(ArgsErr("The arg 'date' must be a date like 2022-01-31."))?,
+            };
+            match u32::try_from(start.days_since(e)) {
+                Ok(d) => day = d,
+                Err(_) => Err(ArgsErr("There is no puzzle before the epoch."))?,
+            }
         }
-        let input_seed = matches.value_of("seed");
-        match input_seed {
+        epoch = Some(e);
+    }
+
+    // arg boards --boards
+    let mut boards: usize = 1;
+    if matches.is_present("boards") {
+        match matches.value_of("boards") {
             None => {
-                if matches.config.seed.is_some() {
-                    seed = matches.config.seed.unwrap();
+                if let Some(b) = matches.config.boards {
+                    boards = b;
                 }
             }
-            Some(s) => match s.parse::<u64>() {
-                Ok(se) => seed = se,
-                Err(_) => return Err(ArgsErr("Your random seed must be a number of type <u64>."))?,
+            Some(b) => match b.parse::<usize>() {
+                Ok(b) => boards = b,
+                Err(_) => Err(ArgsErr("The number of boards must be a number."))?,
             },
         }
-        let mut rng = StdRng::seed_from_u64(seed);
-        final_set.shuffle(&mut rng);
-        key_word = final_set[day as usize].to_string();
-        Wordle::print("Random key: ", tty, Some(true), Some(Color::Blue));
-        Wordle::println(&key_word, tty, Some(true), Some(Color::Green));
+        if !(1..=Wordle::MAX_BOARDS).contains(&boards) || boards > final_set.len() {
+            Err(ArgsErr(
+                "The number of boards must be from 1 to 8 and no more than the final words.",
+            ))?;
+        }
+    }
+
+    // offer to go on with the round left unfinished last time, it's lost otherwise.
+    // Out of a terminal there is no one to ask, so it's lost.
+    let words_id = format!(
+        "{:016x}",
+        PatternMatrix::cache_key(&acceptable_set, &final_set)
+    );
+    let mut resume: Option<Game> = None;
+    if first_tag {
+        if let Some(current) = state.profile_mut(player.as_deref()).current.take() {
+            if current.words.as_deref() != Some(words_id.as_str()) {
+                display::println(
+                    "The unfinished round was played with other word sets, it's counted as lost.",
+                    tty,
+                    Some(true),
+                    Some(Color::Red),
+                );
+            } else if tty {
+                display::print(
+                    &format!(
+                        "Resume the unfinished round of {} guesses?(Y/N): ",
+                        current.guesses.len()
+                    ),
+                    tty,
+                    Some(true),
+                    Some(Color::Blue),
+                );
+                if display::read() == "Y" {
+                    resume = Some(current.clone());
+                }
+            }
+            if resume.is_none() {
+                rounds += 1;
+                let profile = state.profile_mut(player.as_deref());
+                profile.total_rounds += 1;
+                profile.games.push(Game {
+                    finished: Some(Wordle::unix_millis()),
+                    ..current
+                });
+            }
+        }
+    }
+    // the puzzle of a date is played once, the dates of the games and of the
+    // unfinished round are played. Another round goes on with the next date not
+    // played, up to today.
+    if let (Some(epoch), None) = (epoch, &resume) {
+        let profile = state.profile_mut(player.as_deref());
+        let played: HashSet<String> = profile
+            .games
+            .iter()
+            .chain(profile.current.iter())
+            .filter_map(|g| g.date.clone())
+            .collect();
+        while played.contains(&epoch.add_days(day as i64).to_string()) {
+            if first_tag {
+                Err(ArgsErr(
+                    "The puzzle of this date has been played, see the state file.",
+                ))?;
+            }
+            day += 1;
+        }
+        let d = epoch.add_days(day as i64);
+        if !first_tag && d > Date::today() {
+            display::println(
+                "Every puzzle up to today has been played, see you tomorrow.",
+                tty,
+                Some(true),
+                Some(Color::Blue),
+            );
+            return Ok(());
+        }
+        date = Some(d);
+    }
+    // a resumed round is played with its own settings
+    if let Some(game) = &resume {
+        hard_mod = game.difficult || game.ultra_difficult;
+        ultra_hard = game.ultra_difficult;
+        lie = game.lie;
+        seed = game.seed.unwrap_or(seed);
+        boards = game.all_answers().len();
+        date = game.date.as_deref().and_then(|d| d.parse().ok());
+        day = match game.mode {
+            Some(Mode::Date) => game.day.unwrap_or(day),
+            _ => game.day.map_or(day, |d| d.saturating_sub(1)),
+        };
+    }
+
+    // arg: evil --evil, rand_mod --random
+    let key_words: Vec<String>;
+    let evil = match &resume {
+        Some(game) => game.mode == Some(Mode::Evil),
+        None => matches.is_present("evil"),
+    };
+    if let Some(game) = &resume {
+        key_words = game
+            .all_answers()
+            .iter()
+            .map(|a| a.to_lowercase())
+            .collect();
+    } else if evil {
+        if matches.is_present("rand_mod") || matches.is_present("key_word") {
+            Err(ArgsErr("Evil mode and key word modes are conflict."))?;
+        }
+        if boards > 1 {
+            Err(ArgsErr("Evil mode can only be played on one board."))?;
+        }
+        if first_tag {
+            display::println("Evil mode: on", tty, Some(true), Some(Color::Red));
+        }
+        // the game picks the answer while playing
+        key_words = vec![final_set[0].to_string()];
+    } else if matches.is_present("rand_mod") || date.is_some() {
+        if matches.is_present("key_word") {
+            Err(ArgsErr("Random mode and key word input mode are conflict."))?;
+        }
+        if first_tag {
+            display::println(
+                match date {
+                    Some(_) => "Daily puzzle mode",
+                    None => "Random key word mode",
+                },
+                tty,
+                Some(true),
+                Some(Color::Red),
+            );
+        }
+        // every day takes the next `boards` puzzles
+        key_words = (0..boards)
+            .map(|k| {
+                shuffle::nth_answer(&final_set, seed, (day as usize * boards + k) as u64)
+                    .to_string()
+            })
+            .collect();
+        match date {
+            // the daily puzzle keeps its answer secret
+            Some(d) => display::println(
+                &format!("Puzzle #{} of {}", day, d),
+                tty,
+                Some(true),
+                Some(Color::Blue),
+            ),
+            None => {
+                display::print("Random key: ", tty, Some(true), Some(Color::Blue));
+                display::println(&key_words.join(" "), tty, Some(true), Some(Color::Green));
+            }
+        }
     } else {
+        // key words are separated by commas or spaces, one for every board
+        let valid = |input: &str| -> Option<Vec<String>> {
+            let words: Vec<String> = input
+                .split([',', ' '])
+                .filter(|w| !w.is_empty())
+                .map(|w| w.to_string())
+                .collect();
+            let distinct: HashSet<&String> = words.iter().collect();
+            match words.len() == boards
+                && distinct.len() == boards
+                && words
+                    .iter()
+                    .all(|w| w.len() == length && final_set.contains(w))
+            {
+                true => Some(words),
+                false => None,
+            }
+        };
         if matches.is_present("key_word") {
             let input_key_word = matches.value_of("key_word");
             match input_key_word {
                 None => return Err( ArgsErr("No key word found after -w/--word."))?,
                 Some(w) => {
-                    match w.parse::<String>() {
-                        Ok(wd) if wd.len() == 5 && final_set.contains(&wd) => {
+                    match valid(w) {
+                        Some(wds) => {
                             if first_tag {
-                                Wordle::print("Input key word found: ", tty, Some(true), Some(Color::Blue));
-                                Wordle::println(&wd, tty, Some(true), Some(Color::Green));
+                                display::print("Input key word found: ", tty, Some(true), Some(Color::Blue));
+                                display::println(&wds.join(" "), tty, Some(true), Some(Color::Green));
                             }
-                            key_word = wd;
+                            key_words = wds;
                         },
                         _ => return Err( ArgsErr("The input key word has an incorrect format or not be in the final words set."))?,
                     }
@@ -893,48 +1409,205 @@ fn game_day(
             };
         } else {
             loop {
-                Wordle::print(
-                    "Please input your key word: ",
+                display::print(
+                    match boards {
+                        1 => "Please input your key word: ",
+                        _ => "Please input your key words: ",
+                    },
                     tty,
                     Some(true),
                     Some(Color::Blue),
                 );
-                key_word = Wordle::read();
-                if key_word.len() == 5 && final_set.contains(&key_word) {
+                if let Some(wds) = valid(&display::read()) {
+                    key_words = wds;
                     break;
                 } else {
-                    Wordle::println("The input key word has an incorrect format or not be in the final words set.", tty, Some(true), Some(Color::Red));
+                    display::println("The input key word has an incorrect format or not be in the final words set.", tty, Some(true), Some(Color::Red));
                 }
             }
         }
     }
 
-    let mut wordle = Wordle::new(
-        key_word,
+    // arg max_guesses --max-guesses
+    // every board past the first gives a bonus guess, and evil mode has no limit
+    let mut max_guesses: Option<u32> = match evil {
+        true => None,
+        false => Some(Wordle::MAX_GUESSES + boards as u32 - 1),
+    };
+    if matches.is_present("max_guesses") {
+        max_guesses = match matches.value_of("max_guesses") {
+            None => matches.config.max_guesses.filter(|&m| m > 0),
+            Some("unlimited") => None,
+            Some(m) => match m.parse::<u32>() {
+                Ok(0) => None,
+                Ok(m) => Some(m),
+                Err(_) => Err(ArgsErr(
+                    "The arg 'max-guesses' must be a positive number or 'unlimited'.",
+                ))?,
+            },
+        };
+    }
+
+    // arg timed --timed, time_limit --time-limit, guess_time_limit --guess-time-limit
+    let mut timed: Option<TimeLimits> = None;
+    if matches.is_present("timed") {
+        let limit = |arg: &str, conf: Option<u64>| -> Result<Option<u64>, ArgsErr> {
+            if !matches.is_present(arg) {
+                return Ok(None);
+            }
+            match matches.value_of(arg).map(|t| t.parse::<u64>()) {
+                None => Ok(conf.filter(|&t| t > 0)),
+                Some(Ok(t)) if t > 0 => Ok(Some(t)),
+                _ => Err(ArgsErr(
+                    "A time limit must be a positive number of seconds.",
+                )),
+            }
+        };
+        timed = Some(TimeLimits {
+            total: limit("time_limit", matches.config.time_limit)?,
+            per_guess: limit("guess_time_limit", matches.config.guess_time_limit)?,
+        });
+        if first_tag {
+            display::println("Timed mode: on", tty, Some(true), Some(Color::Red));
+        }
+    } else if matches.is_present("time_limit") || matches.is_present("guess_time_limit") {
+        Err(ArgsErr(
+            "--time-limit and --guess-time-limit can only be used in timed mode.",
+        ))?;
+    }
+
+    // a resumed round keeps its own guesses and time limits, a round saved
+    // before the guesses were recorded takes them from the args
+    if let Some(game) = &resume {
+        if let Some(max) = game.max_guesses {
+            max_guesses = Some(max).filter(|&m| m > 0);
+        }
+        timed = game.timed.then_some(TimeLimits {
+            total: game.time_limit,
+            per_guess: game.guess_time_limit,
+        });
+    }
+
+    // arg probes --probes
+    let mut probes = Probes::AnyWord;
+    if matches.is_present("probes") {
+        match matches.value_of("probes").map(|p| p.parse::<Probes>()) {
+            Some(Ok(p)) => probes = p,
+            _ => Err(ArgsErr("The arg 'probes' must be 'any' or 'candidates'."))?,
+        }
+    }
+
+    // the recommender is only shown to the user, so don't build its table in test mode
+    let mut recommender = None;
+    if tty {
+        let cache_dir = match matches.value_of("cache_dir") {
+            Some(dir) => PathBuf::from(dir),
+            None => PatternMatrix::default_cache_dir(),
+        };
+        if first_tag {
+            display::println(
+                "Loading the pattern table...",
+                tty,
+                Some(false),
+                Some(Color::Blue),
+            );
+        }
+        let mut rec = Recommender::new(final_set.clone(), acceptable_set.clone(), &cache_dir)?;
+        if matches.is_present("weights_file") {
+            match matches
+                .value_of("weights_file")
+                .map(recommend::load_weights)
+            {
+                Some(Ok(weights)) => rec.set_weights(&weights),
+                Some(Err(e)) => Err(e)?,
+                None => Err(ArgsErr("No input file of weights found."))?,
+            }
+        }
+        recommender = Some(rec);
+    }
+    let wordle = Wordle {
+        key_words,
         hard_mod,
-        stats,
-        seed,
+        ultra_hard,
         tty,
-        final_set,
         acceptable_set,
-    );
+        recommender,
+        probes,
+        max_guesses,
+        evil: match evil {
+            true => Some(final_set),
+            false => None,
+        },
+        // every round lies differently
+        lie_seed: match lie {
+            true => Some(seed.wrapping_add(day as u64)),
+            false => None,
+        },
+        timed,
+    };
 
-    let (win, try_time, new_game) = wordle.play(&mut words);
+    // the settings of the game, so it can be told apart in the state file
+    let new_game = match resume {
+        Some(game) => game,
+        None => Game {
+            id: Game::new_id(),
+            mode: Some(if evil {
+                Mode::Evil
+            } else if date.is_some() {
+                Mode::Date
+            } else if matches.is_present("rand_mod") {
+                Mode::Random
+            } else {
+                Mode::Word
+            }),
+            seed: match matches.is_present("rand_mod") || date.is_some() || lie {
+                true => Some(seed),
+                false => None,
+            },
+            day: match (date, matches.is_present("rand_mod") || lie) {
+                (Some(_), _) => Some(day),
+                (None, true) => Some(day + 1),
+                _ => None,
+            },
+            date: date.map(|d| d.to_string()),
+            difficult: hard_mod,
+            ultra_difficult: ultra_hard,
+            lie,
+            max_guesses: Some(max_guesses.unwrap_or(0)),
+            timed: timed.is_some(),
+            time_limit: timed.and_then(|t| t.total),
+            guess_time_limit: timed.and_then(|t| t.per_guess),
+            words: Some(words_id),
+            ..Game::new()
+        },
+    };
+
+    // the round is saved as unfinished after every guess
+    let mut save_round = |game: &Game| -> Result<(), Box<dyn std::error::Error>> {
+        if !state_file_path.is_empty() {
+            state.profile_mut(player.as_deref()).current = Some(game.clone());
+            state.save(state_file_path)?;
+        }
+        Ok(())
+    };
+    let (win, try_time, mut new_game) = wordle.play(&mut words, new_game, &mut save_round)?;
+    new_game.finished = Some(Wordle::unix_millis());
     rounds += 1;
     win_rounds += win;
     try_times += try_time;
-    state.total_rounds += 1;
-    state.games.push(new_game);
-    if state_file_path != "".to_string() {
-        let mut state_file = File::create(state_file_path)?;
-        state_file.write_all(serde_json::to_string_pretty(&state)?.as_bytes())?;
+    let profile = state.profile_mut(player.as_deref());
+    profile.current = None;
+    profile.total_rounds += 1;
+    profile.games.push(new_game);
+    if !state_file_path.is_empty() {
+        state.save(state_file_path)?;
     }
 
     // print stats
     if stats {
         // user output
-        Wordle::println("\nYour Stats:", tty, Some(true), Some(Color::Green));
-        Wordle::println(
+        display::println("\nYour Stats:", tty, Some(true), Some(Color::Green));
+        display::println(
             &format!(
                 "Success rate: {}\nAverage trying times: {}",
                 (win_rounds as f32) / (rounds as f32),
@@ -950,7 +1623,7 @@ fn game_day(
         );
 
         // test output
-        Wordle::testout(
+        display::testout(
             &format!(
                 "{} {} {:.2}\n",
                 win_rounds,
@@ -963,7 +1636,68 @@ fn game_day(
             tty,
         );
 
-        Wordle::println("Frequently used words:", tty, Some(true), Some(Color::Blue));
+        // the solve times of the timed games won
+        let times: Vec<u64> = state
+            .profile_mut(player.as_deref())
+            .games
+            .iter()
+            .filter(|g| g.won())
+            .filter_map(|g| g.duration)
+            .collect();
+        if !times.is_empty() {
+            let fastest = Duration::from_millis(*times.iter().min().unwrap());
+            let mean = Duration::from_millis(times.iter().sum::<u64>() / times.len() as u64);
+            display::println(
+                &format!(
+                    "Fastest solve: {}\nMean solve time: {}",
+                    Wordle::fmt_time(fastest),
+                    Wordle::fmt_time(mean)
+                ),
+                tty,
+                None,
+                None,
+            );
+        }
+
+        // the stats panel
+        const BAR_WIDTH: u32 = 20;
+        let panel = state.profile_mut(player.as_deref()).stats();
+        display::println(
+            &format!(
+                "Played: {}  Win %: {}  Current streak: {}  Max streak: {}",
+                panel.played,
+                panel.win_percent(),
+                panel.current_streak,
+                panel.max_streak
+            ),
+            tty,
+            None,
+            None,
+        );
+        display::println("Guess distribution:", tty, Some(true), Some(Color::Blue));
+        let most = panel.distribution.iter().copied().max().unwrap_or(0);
+        for (index, &count) in panel.distribution.iter().enumerate() {
+            display::print(&format!("{:>2} |", index + 1), tty, None, None);
+            // a bar of at least one block for every count above zero
+            let width = (count * BAR_WIDTH).div_ceil(most.max(1));
+            display::print(
+                &"#".repeat(width as usize),
+                tty,
+                Some(true),
+                Some(Color::Green),
+            );
+            display::println(&format!(" {}", count), tty, None, None);
+        }
+        if !panel.failed.is_empty() {
+            display::println(
+                &format!("Failed answers: {}", panel.failed.join(" ")),
+                tty,
+                None,
+                None,
+            );
+        }
+
+        display::println("Frequently used words:", tty, Some(true), Some(Color::Blue));
         let mut count_vec: Vec<(&String, &u32)> = words.iter().collect();
         count_vec.sort_by(|a, b| a.0.cmp(b.0));
         count_vec.sort_by(|a, b| b.1.cmp(a.1));
@@ -972,14 +1706,14 @@ fn game_day(
                 break;
             }
             // user output
-            Wordle::print(
+            display::print(
                 &format!("{}: {}; ", value.0, value.1).to_string(),
                 tty,
                 None,
                 None,
             );
             // test output
-            Wordle::testout(
+            display::testout(
                 &format!(
                     "{}{} {}",
                     match &index {
@@ -992,18 +1726,47 @@ fn game_day(
                 tty,
             );
         }
-        Wordle::println("", tty, None, None);
-        Wordle::testout("\n", tty);
+        display::println("", tty, None, None);
+        display::testout("\n", tty);
+
+        // the stats panel: played, win %, current and max streak, then the
+        // distribution of guesses, then the failed answers
+        display::testout(
+            &format!(
+                "{} {} {} {}\n",
+                panel.played,
+                panel.win_percent(),
+                panel.current_streak,
+                panel.max_streak
+            ),
+            tty,
+        );
+        let distribution: Vec<String> = panel.distribution.iter().map(|c| c.to_string()).collect();
+        display::testout(&format!("{}\n", distribution.join(" ")), tty);
+        display::testout(&format!("{}\n", panel.failed.join(" ")), tty);
+
+        // the fastest and the mean solve time in seconds
+        if matches.is_present("timed") {
+            let seconds = |ms: Option<u64>| ms.unwrap_or(0) as f32 / 1000.0;
+            display::testout(
+                &format!(
+                    "{:.2} {:.2}\n",
+                    seconds(times.iter().min().copied()),
+                    seconds(times.iter().sum::<u64>().checked_div(times.len() as u64))
+                ),
+                tty,
+            );
+        }
     }
 
-    Wordle::print(
+    display::print(
         "Wanna play another round?(Y/N): ",
         tty,
         Some(true),
         Some(Color::Blue),
     );
-    let choose: String = Wordle::read();
-    if choose == "Y".to_string() {
+    let choose: String = display::read();
+    if choose == "Y" {
         game_day(
             matches,
             false,
@@ -1013,13 +1776,96 @@ fn game_day(
             try_times,
             words,
             state,
-            &state_file_path,
+            state_file_path,
+            player,
         )
     } else {
         Ok(())
     }
 }
 
+// the profiles ranked by win rate, then by fewer guesses on average, then by
+// the current streak
+fn leaderboard(state: &State, tty: bool) {
+    let rows: Vec<(&str, Stats)> = state
+        .leaderboard()
+        .into_iter()
+        .map(|(name, stats)| (name.unwrap_or("(default)"), stats))
+        .collect();
+    display::println(
+        &format!(
+            "{:<5}{:<16}{:>7}{:>7}{:>13}{:>8}{:>12}",
+            "Rank", "Player", "Played", "Win %", "Avg guesses", "Streak", "Max streak"
+        ),
+        tty,
+        Some(true),
+        Some(Color::Blue),
+    );
+    for (rank, (name, stats)) in rows.iter().enumerate() {
+        display::println(
+            &format!(
+                "{:<5}{:<16}{:>7}{:>7}{:>13.2}{:>8}{:>12}",
+                rank + 1,
+                name,
+                stats.played,
+                stats.win_percent(),
+                stats.average_guesses(),
+                stats.current_streak,
+                stats.max_streak
+            ),
+            tty,
+            None,
+            None,
+        );
+        display::testout(
+            &format!(
+                "{} {} {} {} {:.2} {} {}\n",
+                rank + 1,
+                name,
+                stats.played,
+                stats.win_percent(),
+                stats.average_guesses(),
+                stats.current_streak,
+                stats.max_streak
+            ),
+            tty,
+        );
+    }
+}
+
+// write the games of the state file as a table, one row per game or per guess
+fn export_games(
+    state: &State,
+    args: &ArgMatches,
+    player: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let format: Format = args.value_of("format").unwrap_or("csv").parse()?;
+    let date = |arg: &str| -> Result<Option<Date>, ArgsErr> {
+        match args.value_of(arg).map(|d| d.parse::<Date>()) {
+            None => Ok(None),
+            Some(Ok(d)) => Ok(Some(d)),
+            Some(Err(_)) => Err(ArgsErr(
+                "The args 'from' and 'to' must be dates like 2022-01-31.",
+            )),
+        }
+    };
+    let filter = Filter {
+        from: date("from")?,
+        to: date("to")?,
+        player: player.map(|p| p.to_string()),
+    };
+    let mut out: Box<dyn Write> = match args.value_of("output") {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+    match args.value_of("rows") {
+        Some("guesses") => export::write(&export::guess_rows(state, &filter), format, &mut out)?,
+        _ => export::write(&export::game_rows(state, &filter), format, &mut out)?,
+    }
+    out.flush()?;
+    Ok(())
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // get the matches of args from command line
     let mut matches = CliApp::new();
@@ -1046,14 +1892,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut day: u32 = 1;
     match matches.value_of("day") {
         None => {
-            if matches.config.day.is_some() {
-                day = matches.config.day.unwrap();
+            if let Some(dy) = matches.config.day {
+                day = dy;
             }
         }
         Some(d) => match d.parse::<u32>() {
             Ok(dy) => {
                 if dy < 1 {
-                    return Err(ArgsErr("The arg 'day' must be a positive integer."))?;
+                    Err(ArgsErr("The arg 'day' must be a positive integer."))?;
                 } else {
                     day = dy;
                 }
@@ -1062,44 +1908,71 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         },
     };
 
+    // a missing state file is created, and on a terminal the games are kept in
+    // the data dir unless a state file is given
+    let tty = atty::is(atty::Stream::Stdout);
+    if matches.is_present("no_state") && matches.is_present("state_file") {
+        Err(ArgsErr("--no-state and -S/--state are conflict."))?;
+    }
     let mut state: State = State::new();
     let mut state_file = "".to_string();
-    match matches.value_of("state_file") {
-        None => {}
-        Some(pwd) => match pwd.parse::<String>() {
-            Ok(path) => match File::open(&path) {
-                Ok(file) => {
-                    state_file = path;
-                    match serde_json::from_reader(BufReader::new(file)) {
-                        Ok(st) => {
-                            state = st;
-                        }
-                        Err(s) => return Err(s)?,
-                    };
-                }
-                Err(_) => return Err(ArgsErr("No input file of previous game state found."))?,
-            },
-            Err(_) => return Err(ArgsErr("File path has a wrong format."))?,
-        },
+    let path = match matches.value_of("state_file") {
+        Some(path) => Some(path.to_string()),
+        None if tty && !matches.is_present("no_state") => {
+            State::default_path().map(|p| p.to_string_lossy().to_string())
+        }
+        None => None,
     };
-    if state.games.len() != (state.total_rounds as usize) {
-        return Err(ArgsErr("Total_rounds and game rounds doesn't match."))?;
+    if let Some(path) = path {
+        match File::open(&path) {
+            Ok(file) => match serde_json::from_reader(BufReader::new(file)) {
+                Ok(st) => state = st,
+                Err(s) => return Err(s)?,
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => display::println(
+                &format!("New state file: {}", path),
+                tty,
+                Some(false),
+                Some(Color::Blue),
+            ),
+            Err(_) => return Err(ArgsErr("Could not open the state file."))?,
+        }
+        state_file = path;
+    }
+    state.migrate()?;
+    if !state.is_consistent() {
+        if !matches.is_present("repair") {
+            Err(ArgsErr(
+                "Total_rounds and game rounds doesn't match, use --repair to fix it.",
+            ))?;
+        }
+        state.repair();
+        display::println(
+            "State file repaired: total rounds counted from the games.",
+            tty,
+            Some(true),
+            Some(Color::Red),
+        );
     }
+    if matches.cli_args.subcommand_matches("leaderboard").is_some() {
+        leaderboard(&state, tty);
+        return Ok(());
+    }
+    if let Some(export_args) = matches.cli_args.subcommand_matches("export") {
+        return export_games(&state, export_args, matches.value_of("player"));
+    }
+
+    // arg player --player
+    let player = matches.value_of("player").map(|p| p.to_string());
+    if player.as_ref().is_some_and(|p| p.trim().is_empty()) {
+        Err(ArgsErr("The player name can't be empty."))?;
+    }
+    let profile = state.profile_mut(player.as_deref());
+    let stats = profile.stats();
     let mut map: HashMap<String, u32> = HashMap::new();
-    let mut win_rounds: u32 = 0;
-    let mut try_times: u32 = 0;
-    for game in &state.games {
-        match game.guesses.len() {
-            0 => {}
-            len => {
-                if game.answer == game.guesses[len - 1] {
-                    win_rounds += 1;
-                    try_times += len as u32;
-                    for word in &game.guesses {
-                        *map.entry(word.to_lowercase()).or_insert(0) += 1;
-                    }
-                }
-            }
+    for game in profile.games.iter().filter(|g| g.won()) {
+        for word in &game.guesses {
+            *map.entry(word.to_lowercase()).or_insert(0) += 1;
         }
     }
 
@@ -1107,11 +1980,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         matches,
         true,
         day - 1,
-        state.total_rounds,
-        win_rounds,
-        try_times,
+        profile.total_rounds,
+        stats.won,
+        stats.guesses,
         map,
         state,
         &state_file,
+        player,
     )
 }
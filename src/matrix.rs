@@ -0,0 +1,220 @@
+use crate::pattern::{score, Pattern};
+use memmap2::Mmap;
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    process,
+    time::Duration,
+};
+
+// the pattern of every guess against every answer, stored row by row in a
+// cache file: MAGIC, key(u64), guesses(u32), answers(u32), then the code of
+// every pair in 1, 2 or 4 little endian bytes as the word length needs. The
+// file is mapped into memory so that a cached table loads at once.
+pub struct PatternMatrix {
+    guesses: usize,
+    answers: usize,
+    len: usize,
+    width: usize,
+    data: Mmap,
+}
+
+// the patterns of one guess against every answer
+#[derive(Clone, Copy)]
+pub struct Row<'a> {
+    bytes: &'a [u8],
+    width: usize,
+}
+
+impl Row<'_> {
+    // the code of the pattern of the answer
+    #[inline]
+    pub fn code(&self, answer: usize) -> usize {
+        let b = &self.bytes[answer * self.width..];
+        match self.width {
+            1 => b[0] as usize,
+            2 => u16::from_le_bytes([b[0], b[1]]) as usize,
+            _ => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize,
+        }
+    }
+}
+
+impl PatternMatrix {
+    const MAGIC: &'static [u8; 8] = b"WDLPAT01";
+    const HEADER: usize = 24;
+    // a temporary table not written for this long was left by a build which
+    // was interrupted
+    const STALE: Duration = Duration::from_secs(600);
+
+    // a stable FNV-1a hash of both word lists, which names the cache file
+    pub fn cache_key(guesses: &[String], answers: &[String]) -> u64 {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for list in [guesses, answers] {
+            for word in list.iter().map(|w| w.as_bytes()).chain([&b"\0"[..]]) {
+                for &b in word.iter().chain(b"\n") {
+                    hash ^= b as u64;
+                    hash = hash.wrapping_mul(0x100000001b3);
+                }
+            }
+        }
+        hash
+    }
+
+    // $XDG_CACHE_HOME/wordle, ~/.cache/wordle or the temp dir as the last choice
+    pub fn default_cache_dir() -> PathBuf {
+        match env::var_os("XDG_CACHE_HOME").filter(|d| !d.is_empty()) {
+            Some(dir) => PathBuf::from(dir).join("wordle"),
+            None => match env::var_os("HOME").filter(|d| !d.is_empty()) {
+                Some(home) => PathBuf::from(home).join(".cache").join("wordle"),
+                None => env::temp_dir().join("wordle"),
+            },
+        }
+    }
+
+    // the bytes of a pattern code of words of the length
+    fn width(len: usize) -> usize {
+        match Pattern::count(len) {
+            c if c <= 1 << 8 => 1,
+            c if c <= 1 << 16 => 2,
+            _ => 4,
+        }
+    }
+
+    // load the table of the word lists from the cache dir, or build and save it.
+    // All the words have the same length.
+    pub fn load_or_build(
+        guesses: &[String],
+        answers: &[String],
+        cache_dir: &Path,
+    ) -> io::Result<PatternMatrix> {
+        let key = PatternMatrix::cache_key(guesses, answers);
+        let len = guesses
+            .first()
+            .or(answers.first())
+            .map_or(Pattern::DEFAULT_LEN, |w| w.len());
+        let path = cache_dir.join(format!("patterns-{:016x}.bin", key));
+        if let Ok(matrix) = PatternMatrix::load(&path, key, guesses.len(), answers.len(), len) {
+            return Ok(matrix);
+        }
+        fs::create_dir_all(cache_dir)?;
+        PatternMatrix::sweep(cache_dir);
+        // write to a temporary file first so that no one maps a half-written table
+        let tmp_path = cache_dir.join(format!("patterns-{:016x}.{}.tmp", key, process::id()));
+        let written = PatternMatrix::write(&tmp_path, key, guesses, answers, len)
+            .and_then(|_| fs::rename(&tmp_path, &path));
+        if let Err(e) = written {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(e);
+        }
+        PatternMatrix::load(&path, key, guesses.len(), answers.len(), len)
+    }
+
+    fn write(
+        path: &Path,
+        key: u64,
+        guesses: &[String],
+        answers: &[String],
+        len: usize,
+    ) -> io::Result<()> {
+        let width = PatternMatrix::width(len);
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(PatternMatrix::MAGIC)?;
+        writer.write_all(&key.to_le_bytes())?;
+        writer.write_all(&(guesses.len() as u32).to_le_bytes())?;
+        writer.write_all(&(answers.len() as u32).to_le_bytes())?;
+        let mut row: Vec<u8> = vec![0; answers.len() * width];
+        for guess in guesses {
+            for (code, answer) in row.chunks_mut(width).zip(answers) {
+                code.copy_from_slice(&score(guess, answer).code().to_le_bytes()[..width]);
+            }
+            writer.write_all(&row)?;
+        }
+        writer.flush()
+    }
+
+    // remove the temporary tables of interrupted builds from the cache dir, the
+    // ones other processes are still writing are recent
+    fn sweep(cache_dir: &Path) {
+        let entries = match fs::read_dir(cache_dir) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let stale = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.elapsed().ok())
+                .is_some_and(|age| age >= PatternMatrix::STALE);
+            if name.starts_with("patterns-") && name.ends_with(".tmp") && stale {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    fn load(
+        path: &Path,
+        key: u64,
+        guesses: usize,
+        answers: usize,
+        len: usize,
+    ) -> io::Result<PatternMatrix> {
+        let file = File::open(path)?;
+        // SAFETY: cache files are only ever replaced by rename, never modified in place
+        let data = unsafe { Mmap::map(&file)? };
+        let width = PatternMatrix::width(len);
+        let valid = data.len() == PatternMatrix::HEADER + guesses * answers * width
+            && &data[0..8] == PatternMatrix::MAGIC
+            && data[8..16] == key.to_le_bytes()
+            && data[16..20] == (guesses as u32).to_le_bytes()
+            && data[20..24] == (answers as u32).to_le_bytes();
+        match valid {
+            true => Ok(PatternMatrix {
+                guesses,
+                answers,
+                len,
+                width,
+                data,
+            }),
+            false => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "The pattern cache file is broken.",
+            )),
+        }
+    }
+
+    pub fn guesses(&self) -> usize {
+        self.guesses
+    }
+
+    pub fn answers(&self) -> usize {
+        self.answers
+    }
+
+    // the length of the words
+    pub fn word_len(&self) -> usize {
+        self.len
+    }
+
+    // the number of patterns a guess can get
+    pub fn patterns(&self) -> usize {
+        Pattern::count(self.len)
+    }
+
+    // the patterns of one guess against every answer
+    pub fn row(&self, guess: usize) -> Row<'_> {
+        let size = self.answers * self.width;
+        let start = PatternMatrix::HEADER + guess * size;
+        Row {
+            bytes: &self.data[start..start + size],
+            width: self.width,
+        }
+    }
+
+    pub fn get(&self, guess: usize, answer: usize) -> Pattern {
+        Pattern::from_code(self.row(guess).code(answer) as u32, self.len).unwrap()
+    }
+}
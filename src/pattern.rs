@@ -0,0 +1,197 @@
+use crate::{feedback::AlphStatus, shuffle::SplitMix64};
+use std::{fmt, str::FromStr};
+
+// the feedback of one guess, encoded in base 3 with the first letter as the
+// lowest digit: 0 -> grey(R), 1 -> yellow(Y), 2 -> green(G)
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pattern {
+    code: u32,
+    len: u8,
+}
+
+impl Pattern {
+    // the length of words when none is given
+    pub const DEFAULT_LEN: usize = 5;
+    pub const MIN_LEN: usize = 4;
+    pub const MAX_LEN: usize = 11;
+
+    // the number of patterns of words of the length, 3^len which present all the states
+    pub fn count(len: usize) -> usize {
+        3usize.pow(len as u32)
+    }
+
+    pub fn all_green(len: usize) -> Pattern {
+        Pattern {
+            code: Pattern::count(len) as u32 - 1,
+            len: len as u8,
+        }
+    }
+
+    pub fn from_code(code: u32, len: usize) -> Option<Pattern> {
+        match (Pattern::MIN_LEN..=Pattern::MAX_LEN).contains(&len)
+            && (code as usize) < Pattern::count(len)
+        {
+            true => Some(Pattern {
+                code,
+                len: len as u8,
+            }),
+            false => None,
+        }
+    }
+
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    // the number of letters
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_win(&self) -> bool {
+        *self == Pattern::all_green(self.len())
+    }
+
+    // the digit of the i-th letter: 0 -> grey, 1 -> yellow, 2 -> green
+    pub fn digit(&self, i: usize) -> u8 {
+        (self.code / 3u32.pow(i as u32) % 3) as u8
+    }
+
+    // the number of letters of different colors in the two patterns
+    pub fn distance(&self, other: &Pattern) -> usize {
+        (0..self.len().min(other.len()))
+            .filter(|&i| self.digit(i) != other.digit(i))
+            .count()
+    }
+
+    // the pattern with exactly one letter of another color, which never looks
+    // like a win. It's picked by our own generator, so a seed gives the same
+    // lies in every version.
+    pub fn lie(&self, rng: &mut SplitMix64) -> Pattern {
+        let mut lies = vec![];
+        for i in 0..self.len() {
+            let unit = 3u32.pow(i as u32);
+            for d in 0..3 {
+                let lie = Pattern {
+                    code: self.code - self.digit(i) as u32 * unit + d * unit,
+                    len: self.len,
+                };
+                if d != self.digit(i) as u32 && !lie.is_win() {
+                    lies.push(lie);
+                }
+            }
+        }
+        match lies.is_empty() {
+            true => *self,
+            false => lies[rng.below(lies.len() as u64) as usize],
+        }
+    }
+
+    pub fn from_status(status: &[AlphStatus]) -> Pattern {
+        let mut code: u32 = 0;
+        for s in status.iter().take(Pattern::MAX_LEN).rev() {
+            code = code * 3
+                + match s {
+                    AlphStatus::Right => 2,
+                    AlphStatus::PosWrong => 1,
+                    _ => 0,
+                };
+        }
+        Pattern {
+            code,
+            len: status.len().min(Pattern::MAX_LEN) as u8,
+        }
+    }
+
+    pub fn to_status(&self) -> Vec<AlphStatus> {
+        (0..self.len())
+            .map(|i| match self.digit(i) {
+                2 => AlphStatus::Right,
+                1 => AlphStatus::PosWrong,
+                _ => AlphStatus::TooMany,
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for s in self.to_status() {
+            write!(f, "{}", s.parse3())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParsePatternErr;
+
+impl fmt::Display for ParsePatternErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a pattern should be {} to {} letters of G, Y or R",
+            Pattern::MIN_LEN,
+            Pattern::MAX_LEN
+        )
+    }
+}
+
+impl std::error::Error for ParsePatternErr {}
+
+impl FromStr for Pattern {
+    type Err = ParsePatternErr;
+
+    // parse a pattern like "GYRRR", case insensitive
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !(Pattern::MIN_LEN..=Pattern::MAX_LEN).contains(&s.len()) {
+            return Err(ParsePatternErr);
+        }
+        let mut status = vec![];
+        for c in s.chars() {
+            status.push(match c.to_ascii_uppercase() {
+                'G' => AlphStatus::Right,
+                'Y' => AlphStatus::PosWrong,
+                'R' => AlphStatus::TooMany,
+                _ => return Err(ParsePatternErr),
+            });
+        }
+        Ok(Pattern::from_status(&status))
+    }
+}
+
+// the feedback of the guess if the key word is the answer: greens are marked
+// first, then every other letter is yellow only while the answer still has
+// an unmatched copy of it, from left to right. Both words have the same length.
+pub fn score(guess: &str, answer: &str) -> Pattern {
+    let guess = guess.as_bytes();
+    let answer = answer.as_bytes();
+    let len = guess.len().min(answer.len()).min(Pattern::MAX_LEN);
+    let mut digits = [0u8; Pattern::MAX_LEN];
+    let mut left = [0u8; 256];
+    for i in 0..len {
+        if guess[i] == answer[i] {
+            digits[i] = 2;
+        } else {
+            left[answer[i] as usize] += 1;
+        }
+    }
+    for i in 0..len {
+        if digits[i] != 2 && left[guess[i] as usize] > 0 {
+            digits[i] = 1;
+            left[guess[i] as usize] -= 1;
+        }
+    }
+    let mut code: u32 = 0;
+    for &d in digits[..len].iter().rev() {
+        code = code * 3 + d as u32;
+    }
+    Pattern {
+        code,
+        len: len as u8,
+    }
+}
@@ -0,0 +1,266 @@
+use crate::{
+    constraints::Constraints,
+    matrix::PatternMatrix,
+    pattern::{score, Pattern},
+    wordset::WordSet,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+};
+
+// load the weight of words from lines like "crane 0.0012", the higher the more
+// likely the word is the answer
+pub fn load_weights(path: impl AsRef<Path>) -> io::Result<HashMap<String, f32>> {
+    let mut weights = HashMap::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        let mut parts = line.split_whitespace();
+        let (word, weight) = match (parts.next(), parts.next(), parts.next()) {
+            (None, _, _) => continue,
+            (Some(word), Some(weight), None) => (word, weight),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Wrong line in the weights file: '{}'.", line),
+                ))
+            }
+        };
+        match weight.parse::<f32>() {
+            Ok(w) if w >= 0.0 && w.is_finite() => {
+                weights.insert(word.to_lowercase(), w);
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Wrong weight in the weights file: '{}'.", line),
+                ))
+            }
+        }
+    }
+    Ok(weights)
+}
+
+// split the words by the pattern they give to the guess, from the least
+// pattern to the greatest
+pub fn partition(guess: &str, words: &[String]) -> Vec<(Pattern, Vec<String>)> {
+    let mut buckets: BTreeMap<Pattern, Vec<String>> = BTreeMap::new();
+    for word in words {
+        buckets
+            .entry(score(guess, word))
+            .or_default()
+            .push(word.to_string());
+    }
+    buckets.into_iter().collect()
+}
+
+// ranks guesses with the pattern table of the acceptable set against the final
+// set, where the final set is the prior of the answer with a weight per word
+pub struct Recommender {
+    final_set: Vec<String>,
+    acceptable_set: Vec<String>,
+    rows: HashMap<String, usize>,
+    final_rows: WordSet,
+    weights: Vec<f32>,
+    matrix: PatternMatrix,
+}
+
+impl Recommender {
+    pub fn new(
+        final_set: Vec<String>,
+        acceptable_set: Vec<String>,
+        cache_dir: &Path,
+    ) -> io::Result<Recommender> {
+        let matrix = PatternMatrix::load_or_build(&acceptable_set, &final_set, cache_dir)?;
+        let rows: HashMap<String, usize> = acceptable_set
+            .iter()
+            .enumerate()
+            .map(|(i, w)| (w.to_string(), i))
+            .collect();
+        let mut final_rows = WordSet::new(acceptable_set.len());
+        for row in final_set.iter().filter_map(|w| rows.get(w)) {
+            final_rows.insert(*row);
+        }
+        Ok(Recommender {
+            weights: vec![1.0; final_set.len()],
+            final_set,
+            acceptable_set,
+            rows,
+            final_rows,
+            matrix,
+        })
+    }
+
+    // weigh the words of the final set, a word without a weight gets the
+    // smallest weight given so that it is unlikely but still possible
+    pub fn set_weights(&mut self, weights: &HashMap<String, f32>) {
+        let least = weights
+            .values()
+            .copied()
+            .filter(|&w| w > 0.0)
+            .fold(f32::INFINITY, f32::min);
+        let least = match least.is_finite() {
+            true => least,
+            false => 1.0,
+        };
+        for (word, weight) in self.final_set.iter().zip(self.weights.iter_mut()) {
+            *weight = weights.get(word).copied().unwrap_or(least);
+        }
+    }
+
+    // the weight of the word in the final set
+    pub fn weight(&self, answer: usize) -> f32 {
+        self.weights[answer]
+    }
+
+    pub fn final_set(&self) -> &[String] {
+        &self.final_set
+    }
+
+    pub fn acceptable_set(&self) -> &[String] {
+        &self.acceptable_set
+    }
+
+    pub fn matrix(&self) -> &PatternMatrix {
+        &self.matrix
+    }
+
+    // the index of the word in the acceptable set, which is its row in the table
+    pub fn row(&self, word: &str) -> Option<usize> {
+        self.rows.get(word).copied()
+    }
+
+    // the words in the final set which are still possible
+    pub fn candidates(&self, possible: impl Fn(&str) -> bool) -> WordSet {
+        let mut set = WordSet::new(self.final_set.len());
+        for (i, word) in self.final_set.iter().enumerate() {
+            if possible(word) {
+                set.insert(i);
+            }
+        }
+        set
+    }
+
+    // the candidates from the most likely to the least likely
+    pub fn possible_answers(&self, candidates: &WordSet) -> Vec<&String> {
+        let mut answers: Vec<usize> = candidates.iter().collect();
+        answers.sort_by(|&a, &b| {
+            self.weights[b]
+                .partial_cmp(&self.weights[a])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        answers.into_iter().map(|i| &self.final_set[i]).collect()
+    }
+
+    // the acceptable words out of the final set which still match the feedback,
+    // they can be guessed but never be the answer
+    pub fn other_words(&self, possible: impl Fn(&str) -> bool) -> Vec<&String> {
+        self.acceptable_set
+            .iter()
+            .enumerate()
+            .filter(|&(row, word)| !self.final_rows.contains(row) && possible(word))
+            .map(|(_, word)| word)
+            .collect()
+    }
+
+    // shanon information entropy of the patterns the guess splits the candidates
+    // into, every candidate counts as much as its weight
+    pub fn entropy(&self, guess: usize, candidates: &WordSet) -> f32 {
+        let row = self.matrix.row(guess);
+        let mut cnt: Vec<f32> = vec![0.0; self.matrix.patterns()];
+        let mut total: f32 = 0.0;
+        for answer in candidates.iter() {
+            cnt[row.code(answer)] += self.weights[answer];
+            total += self.weights[answer];
+        }
+        let mut ans: f32 = 0.0;
+        for c in cnt {
+            if c > 0.0 {
+                ans -= c / total * (c / total).log2();
+            }
+        }
+        ans
+    }
+
+    // the words allowed as the next guess, as rows of the table. In hard mode
+    // they must also keep to the constraints of every board, ultra hard mode
+    // or not.
+    pub fn probes(
+        &self,
+        candidates: &WordSet,
+        probes: Probes,
+        hard_mode: Option<(&[&Constraints], bool)>,
+    ) -> WordSet {
+        let mut set = WordSet::new(self.acceptable_set.len());
+        match probes {
+            Probes::Candidates => {
+                for row in candidates
+                    .iter()
+                    .filter_map(|i| self.row(&self.final_set[i]))
+                {
+                    set.insert(row);
+                }
+            }
+            Probes::AnyWord => set = WordSet::full(self.acceptable_set.len()),
+        }
+        if let Some((boards, ultra)) = hard_mode {
+            for row in set.clone().iter() {
+                let word = &self.acceptable_set[row];
+                if boards.iter().any(|c| c.check_hard(word, ultra).is_err()) {
+                    set.remove(row);
+                }
+            }
+        }
+        set
+    }
+
+    // rank the probes by the entropy they get over the candidates, the best
+    // comes first and a probe which may be the key word wins a tie
+    pub fn recommend_words(&self, candidates: &WordSet, probes: &WordSet) -> Vec<(String, f32)> {
+        let is_candidate: WordSet = self.probes(candidates, Probes::Candidates, None);
+        let mut count_vec: Vec<(String, f32, bool)> = probes
+            .iter()
+            .map(|row| {
+                (
+                    self.acceptable_set[row].to_string(),
+                    self.entropy(row, candidates),
+                    is_candidate.contains(row),
+                )
+            })
+            .collect();
+        count_vec.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(b.2.cmp(&a.2))
+                .then(a.0.cmp(&b.0))
+        });
+        count_vec.into_iter().map(|(w, e, _)| (w, e)).collect()
+    }
+}
+
+// which words the recommender ranks as the next guess
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Probes {
+    // only the words which may still be the key word
+    Candidates,
+    // every acceptable word, which may tell more even if it can't win
+    AnyWord,
+}
+
+impl FromStr for Probes {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "candidates" => Ok(Probes::Candidates),
+            "any" => Ok(Probes::AnyWord),
+            _ => Err(format!(
+                "Unknown probes '{}', use 'candidates' or 'any'.",
+                s
+            )),
+        }
+    }
+}
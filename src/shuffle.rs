@@ -0,0 +1,62 @@
+// The order the answers of seeded games are taken in. It is defined here rather
+// than by a random crate, so a seed and a day give the same word forever:
+//
+// 1. SplitMix64 makes the numbers: the state starts at the seed, and every number
+//    adds 0x9e3779b97f4a7c15 to the state and mixes it by
+//    z = (z ^ z >> 30) * 0xbf58476d1ce4e5b9, z = (z ^ z >> 27) * 0x94d049bb133111eb,
+//    z ^ z >> 31, all wrapping on 64 bits.
+// 2. A number below n is a number x >= 2^64 mod n, taken as x mod n, the numbers
+//    under it are dropped so every result is as likely.
+// 3. The words are shuffled by Fisher-Yates: for i from n - 1 down to 1, the i-th
+//    word is swapped with the j-th, j a number below i + 1.
+// 4. The answer of the k-th puzzle is the (k mod n)-th word of the shuffled words.
+//
+// The order of the words given matters, the word set files are loaded sorted.
+// Changing any step changes the answers of every seed, and the golden tests in
+// tests/shuffle.rs are there to catch it.
+
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // a number below the bound, which is positive
+    pub fn below(&mut self, bound: u64) -> u64 {
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let x = self.next_u64();
+            if x >= threshold {
+                return x % bound;
+            }
+        }
+    }
+}
+
+// the items in the order of the seed
+pub fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut rng = SplitMix64::new(seed);
+    for i in (1..items.len()).rev() {
+        let j = rng.below(i as u64 + 1) as usize;
+        items.swap(i, j);
+    }
+}
+
+// the answer of the index-th puzzle of the seed, the final set isn't empty
+pub fn nth_answer(final_set: &[String], seed: u64, index: u64) -> &str {
+    let mut order: Vec<usize> = (0..final_set.len()).collect();
+    shuffle(&mut order, seed);
+    &final_set[order[(index % order.len() as u64) as usize]]
+}
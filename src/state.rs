@@ -0,0 +1,393 @@
+use serde_derive::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    env,
+    error::Error,
+    fs::{self, File, OpenOptions},
+    io::{BufReader, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicU32, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+// how the key word of a game was chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    // given by -w/--word or typed in
+    Word,
+    Random,
+    Date,
+    Evil,
+}
+
+fn is_false(b: &bool) -> bool {
+    !b
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Game {
+    // tells the game apart from the ones other processes save to the same file,
+    // games from before it was recorded have none
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    // the answer of the first board
+    pub answer: String,
+    // the answers of every board, only recorded when there are several
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub answers: Vec<String>,
+    pub guesses: Vec<String>,
+    // the settings of the game, which games of version 1 don't have
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<Mode>,
+    // the seed of a random, date or lying game
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    // the -d/--day of a random game, the puzzle number of a date game
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day: Option<u32>,
+    // in date mode, the date of the puzzle like "2022-01-31"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(skip_serializing_if = "is_false")]
+    pub difficult: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub ultra_difficult: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub lie: bool,
+    // the guesses the game allows, 0 for unlimited
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_guesses: Option<u32>,
+    // timed mode and its limits in seconds
+    #[serde(skip_serializing_if = "is_false")]
+    pub timed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guess_time_limit: Option<u64>,
+    // the cache key of the acceptable and the final set in hex, which tells
+    // the word sets apart
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub words: Option<String>,
+    // when the game was over in unix milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished: Option<u64>,
+    // in timed mode, when every guess was made in unix milliseconds
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub timestamps: Vec<u64>,
+    // in timed mode, how long the game took in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
+}
+
+impl Game {
+    pub fn new() -> Game {
+        Game::default()
+    }
+
+    // an id from the time, the process and a count of the games of the process
+    pub fn new_id() -> String {
+        static COUNT: AtomicU32 = AtomicU32::new(0);
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |t| t.as_millis());
+        format!(
+            "{:x}-{:x}-{:x}",
+            millis,
+            process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        )
+    }
+
+    // the answers of every board
+    pub fn all_answers(&self) -> Vec<&String> {
+        match self.answers.is_empty() {
+            true => vec![&self.answer],
+            false => self.answers.iter().collect(),
+        }
+    }
+
+    // whether every board was solved, the last guess solves the last board
+    pub fn won(&self) -> bool {
+        match self.guesses.last() {
+            None => false,
+            Some(last) => {
+                let answers = self.all_answers();
+                answers.contains(&last) && answers.iter().all(|a| self.guesses.contains(a))
+            }
+        }
+    }
+}
+
+// the games of a player
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Profile {
+    pub total_rounds: u32,
+    pub games: Vec<Game>,
+    // the round being played, saved after every guess so it can be resumed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<Game>,
+}
+
+impl Profile {
+    pub fn new() -> Profile {
+        Profile::default()
+    }
+
+    // the games of the saved profile, then the games of this one it doesn't have.
+    // Games without an id were all read from the file, so they are in it already.
+    // The round saved as unfinished is kept while it's not over and this profile
+    // has none of its own.
+    pub fn merge(&mut self, saved: Profile) {
+        let ids: HashSet<String> = saved
+            .games
+            .iter()
+            .filter(|g| !g.id.is_empty())
+            .map(|g| g.id.to_string())
+            .collect();
+        let mut games = saved.games;
+        games.extend(
+            self.games
+                .drain(..)
+                .filter(|g| !g.id.is_empty() && !ids.contains(&g.id)),
+        );
+        self.games = games;
+        self.total_rounds = self.games.len() as u32;
+        if self.current.is_none() {
+            self.current = saved
+                .current
+                .filter(|c| !self.games.iter().any(|g| g.id == c.id));
+        }
+    }
+
+    // whether the total rounds agree with the games
+    pub fn is_consistent(&self) -> bool {
+        self.total_rounds as usize == self.games.len()
+    }
+
+    // count the total rounds from the games, which are what is played
+    pub fn repair(&mut self) {
+        self.total_rounds = self.games.len() as u32;
+    }
+
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats {
+            played: self.games.len() as u32,
+            won: 0,
+            guesses: 0,
+            current_streak: 0,
+            max_streak: 0,
+            distribution: vec![0; Stats::MIN_GUESSES],
+            failed: vec![],
+        };
+        for game in &self.games {
+            if game.won() {
+                stats.won += 1;
+                stats.current_streak += 1;
+                stats.max_streak = stats.max_streak.max(stats.current_streak);
+                let guesses = game.guesses.len();
+                stats.guesses += guesses as u32;
+                if guesses > stats.distribution.len() {
+                    stats.distribution.resize(guesses, 0);
+                }
+                stats.distribution[guesses - 1] += 1;
+            } else {
+                stats.current_streak = 0;
+                stats.failed.extend(
+                    game.all_answers()
+                        .into_iter()
+                        .filter(|a| !game.guesses.contains(a))
+                        .cloned(),
+                );
+            }
+        }
+        stats
+    }
+}
+
+// the games played, as saved in the state file. Version 1 had no version field
+// and no settings of the games, version 2 had no players.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct State {
+    pub version: u32,
+    // the games played without a player name
+    #[serde(flatten)]
+    pub default: Profile,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub players: BTreeMap<String, Profile>,
+}
+
+impl State {
+    pub const VERSION: u32 = 3;
+
+    pub fn new() -> State {
+        State {
+            version: State::VERSION,
+            default: Profile::new(),
+            players: BTreeMap::new(),
+        }
+    }
+
+    // $XDG_DATA_HOME/wordle/state.json or ~/.local/share/wordle/state.json,
+    // None without a home
+    pub fn default_path() -> Option<PathBuf> {
+        let dir = match env::var_os("XDG_DATA_HOME").filter(|d| !d.is_empty()) {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(env::var_os("HOME").filter(|d| !d.is_empty())?)
+                .join(".local")
+                .join("share"),
+        };
+        Some(dir.join("wordle").join("state.json"))
+    }
+
+    // bring a state of an older version up to this one
+    pub fn migrate(&mut self) -> Result<(), String> {
+        match self.version {
+            // version 1 games have no settings, which stay unknown, and the games
+            // of version 1 and 2 are the ones of the default profile
+            0..=2 => self.version = State::VERSION,
+            State::VERSION => {}
+            v => {
+                return Err(format!(
+                    "The state file has version {}, this game only reads up to version {}.",
+                    v,
+                    State::VERSION
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    // the profile of the player, or the default one, made if it's new
+    pub fn profile_mut(&mut self, player: Option<&str>) -> &mut Profile {
+        match player {
+            None => &mut self.default,
+            Some(name) => self.players.entry(name.to_string()).or_default(),
+        }
+    }
+
+    // the stats of the profiles ranked by win rate, then by fewer guesses on
+    // average, then by the current streak and the name. The profiles with no
+    // game over come last.
+    pub fn leaderboard(&self) -> Vec<(Option<&str>, Stats)> {
+        let mut rows: Vec<(Option<&str>, Stats)> = self
+            .profiles()
+            .into_iter()
+            .map(|(name, profile)| (name, profile.stats()))
+            .collect();
+        rows.sort_by(|(a_name, a), (b_name, b)| {
+            (a.played == 0)
+                .cmp(&(b.played == 0))
+                .then(b.win_rate().total_cmp(&a.win_rate()))
+                .then(a.average_guesses().total_cmp(&b.average_guesses()))
+                .then(b.current_streak.cmp(&a.current_streak))
+                .then(a_name.cmp(b_name))
+        });
+        rows
+    }
+
+    // the default profile if it has games, then the players by name
+    pub fn profiles(&self) -> Vec<(Option<&str>, &Profile)> {
+        let mut profiles: Vec<(Option<&str>, &Profile)> = vec![];
+        if !self.default.games.is_empty() {
+            profiles.push((None, &self.default));
+        }
+        profiles.extend(self.players.iter().map(|(n, p)| (Some(n.as_str()), p)));
+        profiles
+    }
+
+    // write the state to the file after taking in the games other processes
+    // saved there meanwhile. The file is locked by an advisory lock on a
+    // ".lock" file next to it, and replaced by renaming a temporary file, so a
+    // crash never leaves it half written.
+    pub fn save(&mut self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        let path = path.as_ref();
+        let sibling = |suffix: &str| -> PathBuf {
+            let mut name = path.file_name().unwrap_or_default().to_os_string();
+            name.push(suffix);
+            path.with_file_name(name)
+        };
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let lock = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(sibling(".lock"))?;
+        lock.lock()?;
+        if let Ok(file) = File::open(path) {
+            let mut saved: State = serde_json::from_reader(BufReader::new(file))?;
+            saved.migrate()?;
+            self.merge(saved);
+        }
+        let tmp = sibling(&format!(".{}.tmp", process::id()));
+        let mut file = File::create(&tmp)?;
+        file.write_all(serde_json::to_string_pretty(self)?.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    // merge every profile of the saved state into this one
+    pub fn merge(&mut self, saved: State) {
+        self.default.merge(saved.default);
+        for (name, profile) in saved.players {
+            self.players.entry(name).or_default().merge(profile);
+        }
+    }
+
+    // whether the total rounds of every profile agree with its games
+    pub fn is_consistent(&self) -> bool {
+        self.default.is_consistent() && self.players.values().all(|p| p.is_consistent())
+    }
+
+    pub fn repair(&mut self) {
+        self.default.repair();
+        self.players.values_mut().for_each(|p| p.repair());
+    }
+}
+
+// the stats panel of the games of a profile
+#[derive(Debug, Clone)]
+pub struct Stats {
+    pub played: u32,
+    pub won: u32,
+    // the guesses of the games won
+    pub guesses: u32,
+    // the games won in a row, up to the last one
+    pub current_streak: u32,
+    pub max_streak: u32,
+    // the games won in 1, 2... guesses, at least up to 6
+    pub distribution: Vec<u32>,
+    // the answers of the games lost, oldest first
+    pub failed: Vec<String>,
+}
+
+impl Stats {
+    const MIN_GUESSES: usize = 6;
+
+    pub fn win_percent(&self) -> u32 {
+        (self.won * 100).checked_div(self.played).unwrap_or(0)
+    }
+
+    // the share of the games won, 0 without games
+    pub fn win_rate(&self) -> f64 {
+        match self.played {
+            0 => 0.0,
+            _ => self.won as f64 / self.played as f64,
+        }
+    }
+
+    // the guesses a game won takes on average
+    pub fn average_guesses(&self) -> f32 {
+        match self.won {
+            0 => 0.0,
+            _ => self.guesses as f32 / self.won as f32,
+        }
+    }
+}
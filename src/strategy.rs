@@ -0,0 +1,326 @@
+use crate::{
+    constraints::Constraints,
+    feedback::fits_one_lie,
+    pattern::Pattern,
+    recommend::{Probes, Recommender},
+    tree::{Objective, TreeSearch},
+    wordset::WordSet,
+};
+use rand::{rngs::StdRng, seq::IteratorRandom, SeedableRng};
+use std::{collections::HashMap, str::FromStr};
+
+// the guesses of a game so far, with the patterns they got
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    guesses: Vec<(String, Pattern)>,
+    constraints: Constraints,
+    lies: bool,
+}
+
+impl History {
+    pub fn new() -> History {
+        History::default()
+    }
+
+    // the history of a game where every pattern has exactly one lie
+    pub fn lying() -> History {
+        History {
+            lies: true,
+            ..History::default()
+        }
+    }
+
+    pub fn push(&mut self, guess: &str, pattern: Pattern) {
+        self.constraints.apply(guess, pattern);
+        self.guesses.push((guess.to_string(), pattern));
+    }
+
+    pub fn guesses(&self) -> &[(String, Pattern)] {
+        &self.guesses
+    }
+
+    pub fn len(&self) -> usize {
+        self.guesses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.guesses.is_empty()
+    }
+
+    // what the key word must look like after the guesses, if they tell the truth
+    pub fn constraints(&self) -> &Constraints {
+        &self.constraints
+    }
+
+    // whether the word could still be the key word
+    pub fn possible(&self, word: &str) -> bool {
+        match self.lies {
+            true => fits_one_lie(word, &self.guesses),
+            false => self.constraints.matches(word),
+        }
+    }
+
+    // the words of the final set which could still be the key word
+    pub fn candidates(&self, recommender: &Recommender) -> WordSet {
+        recommender.candidates(|w| self.possible(w))
+    }
+}
+
+// a policy picking the next guess of a game
+pub trait Strategy {
+    // the next word to guess, None if it can't find the key word from here
+    fn next_guess(&mut self, recommender: &Recommender, history: &History) -> Option<String>;
+}
+
+// what a greedy strategy minimizes over the probes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    // the information left, so it takes the most entropy
+    Entropy,
+    // the number of candidates of the largest pattern
+    Minimax,
+    // the expected number of candidates left
+    ExpectedSize,
+    // how rare the letters of the guess are among the candidates
+    Frequency,
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "entropy" => Ok(Rule::Entropy),
+            "minimax" => Ok(Rule::Minimax),
+            "expected" => Ok(Rule::ExpectedSize),
+            "frequency" => Ok(Rule::Frequency),
+            _ => Err(format!(
+                "Unknown rule '{}', use 'entropy', 'minimax', 'expected' or 'frequency'.",
+                s
+            )),
+        }
+    }
+}
+
+// the probe with the best score by the rule, a probe which may be the key word
+// and then the earlier one in the acceptable set win a tie. The best guess of
+// every set of candidates is remembered, as a game meets the same sets often.
+pub struct Greedy {
+    rule: Rule,
+    probes: Probes,
+    memo: HashMap<WordSet, Option<usize>>,
+}
+
+impl Greedy {
+    pub fn new(rule: Rule, probes: Probes) -> Greedy {
+        Greedy {
+            rule,
+            probes,
+            memo: HashMap::new(),
+        }
+    }
+
+    // the probe with the least score by the rule
+    fn best(&self, recommender: &Recommender, candidates: &WordSet) -> Option<usize> {
+        let is_candidate = recommender.probes(candidates, Probes::Candidates, None);
+        let probes = recommender.probes(candidates, self.probes, None);
+        // how many candidates have the letter, and have it at the position
+        let mut letters = [0u32; 26];
+        let mut places = [[0u32; 26]; Pattern::MAX_LEN];
+        if self.rule == Rule::Frequency {
+            for answer in candidates.iter() {
+                let word = recommender.final_set()[answer].as_bytes();
+                let mut seen = 0u32;
+                for (i, &b) in word.iter().enumerate() {
+                    let l = (b - b'a') as usize;
+                    places[i][l] += 1;
+                    if seen >> l & 1 == 0 {
+                        letters[l] += 1;
+                        seen |= 1 << l;
+                    }
+                }
+            }
+        }
+        let mut cnt: Vec<f32> = vec![0.0; recommender.matrix().patterns()];
+        let mut best: Option<(f32, bool, usize)> = None;
+        for guess in probes.iter() {
+            let score = match self.rule {
+                Rule::Entropy => -recommender.entropy(guess, candidates),
+                Rule::Minimax | Rule::ExpectedSize => {
+                    let row = recommender.matrix().row(guess);
+                    cnt.iter_mut().for_each(|c| *c = 0.0);
+                    let mut total: f32 = 0.0;
+                    for answer in candidates.iter() {
+                        let w = match self.rule {
+                            Rule::Minimax => 1.0,
+                            _ => recommender.weight(answer),
+                        };
+                        cnt[row.code(answer)] += w;
+                        total += w;
+                    }
+                    match self.rule {
+                        Rule::Minimax => cnt.iter().copied().fold(0.0, f32::max),
+                        _ => cnt.iter().map(|c| c * c).sum::<f32>() / total,
+                    }
+                }
+                Rule::Frequency => {
+                    let word = recommender.acceptable_set()[guess].as_bytes();
+                    let mut seen = 0u32;
+                    let mut score: u32 = 0;
+                    for (i, &b) in word.iter().enumerate() {
+                        let l = (b - b'a') as usize;
+                        score += places[i][l];
+                        if seen >> l & 1 == 0 {
+                            score += letters[l];
+                            seen |= 1 << l;
+                        }
+                    }
+                    -(score as f32)
+                }
+            };
+            let key = (score, !is_candidate.contains(guess), guess);
+            if best.is_none_or(|b| key.partial_cmp(&b) == Some(std::cmp::Ordering::Less)) {
+                best = Some(key);
+            }
+        }
+        best.map(|(_, _, guess)| guess)
+    }
+}
+
+impl Strategy for Greedy {
+    fn next_guess(&mut self, recommender: &Recommender, history: &History) -> Option<String> {
+        let candidates = history.candidates(recommender);
+        let guess = match self.memo.get(&candidates) {
+            Some(&guess) => guess,
+            None => {
+                let guess = self.best(recommender, &candidates);
+                self.memo.insert(candidates, guess);
+                guess
+            }
+        };
+        Some(recommender.acceptable_set()[guess?].to_string())
+    }
+}
+
+// a random word which may still be the key word
+pub struct RandomCandidate {
+    rng: StdRng,
+}
+
+impl RandomCandidate {
+    pub fn new(seed: u64) -> RandomCandidate {
+        RandomCandidate {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for RandomCandidate {
+    fn next_guess(&mut self, recommender: &Recommender, history: &History) -> Option<String> {
+        let candidates = history.candidates(recommender);
+        let answer = candidates.iter().choose(&mut self.rng)?;
+        Some(recommender.final_set()[answer].to_string())
+    }
+}
+
+// the guess of the decision tree search, which must find the key word in the
+// guesses left
+pub struct Optimal {
+    search: TreeSearch,
+    max_guesses: u32,
+}
+
+impl Optimal {
+    pub fn new(search: TreeSearch, max_guesses: u32) -> Optimal {
+        Optimal {
+            search,
+            max_guesses,
+        }
+    }
+}
+
+impl Strategy for Optimal {
+    fn next_guess(&mut self, recommender: &Recommender, history: &History) -> Option<String> {
+        let candidates = history.candidates(recommender);
+        let guesses_left = self.max_guesses.saturating_sub(history.len() as u32);
+        let (guess, _) = self
+            .search
+            .best_guess(recommender, &candidates, guesses_left)?;
+        Some(recommender.acceptable_set()[guess].to_string())
+    }
+}
+
+// the settings a strategy is made with, each one takes what it needs
+#[derive(Debug, Clone, Copy)]
+pub struct StrategyOptions {
+    pub probes: Probes,
+    pub objective: Objective,
+    pub width: usize,
+    pub max_guesses: u32,
+    pub seed: u64,
+}
+
+type Maker = Box<dyn Fn(&StrategyOptions) -> Box<dyn Strategy>>;
+
+// the strategies which can be picked by name
+pub struct Strategies {
+    makers: Vec<(String, Maker)>,
+}
+
+impl Default for Strategies {
+    fn default() -> Self {
+        Strategies::new()
+    }
+}
+
+impl Strategies {
+    // the built-in strategies
+    pub fn new() -> Strategies {
+        let mut strategies = Strategies { makers: vec![] };
+        for name in ["entropy", "minimax", "expected", "frequency"] {
+            let rule: Rule = name.parse().unwrap();
+            strategies.register(name, move |options| {
+                Box::new(Greedy::new(rule, options.probes))
+            });
+        }
+        strategies.register("random", |options| {
+            Box::new(RandomCandidate::new(options.seed))
+        });
+        strategies.register("optimal", |options| {
+            Box::new(Optimal::new(
+                TreeSearch::new(options.objective, options.width),
+                options.max_guesses,
+            ))
+        });
+        strategies
+    }
+
+    // add a strategy, or replace the one with the same name
+    pub fn register(
+        &mut self,
+        name: &str,
+        maker: impl Fn(&StrategyOptions) -> Box<dyn Strategy> + 'static,
+    ) {
+        self.makers.retain(|(n, _)| n != name);
+        self.makers.push((name.to_string(), Box::new(maker)));
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.makers.iter().map(|(n, _)| n.as_str()).collect()
+    }
+
+    pub fn create(
+        &self,
+        name: &str,
+        options: &StrategyOptions,
+    ) -> Result<Box<dyn Strategy>, String> {
+        match self.makers.iter().find(|(n, _)| n == name) {
+            Some((_, maker)) => Ok(maker(options)),
+            None => Err(format!(
+                "Unknown strategy '{}', use one of {}.",
+                name,
+                self.names().join(", ")
+            )),
+        }
+    }
+}
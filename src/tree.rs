@@ -0,0 +1,298 @@
+use crate::{pattern::Pattern, recommend::Recommender, wordset::WordSet};
+use std::{collections::HashMap, fmt, str::FromStr};
+
+// what the search minimizes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    // the total (so the mean) number of guesses over all the answers
+    Expected,
+    // the number of guesses of the hardest answer
+    WorstCase,
+}
+
+impl FromStr for Objective {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "expected" => Ok(Objective::Expected),
+            "worst" => Ok(Objective::WorstCase),
+            _ => Err(format!(
+                "Unknown objective '{}', use 'expected' or 'worst'.",
+                s
+            )),
+        }
+    }
+}
+
+// the cost of a set which can't be solved in the guesses left
+const INF: u32 = u32::MAX / 2;
+
+// a depth limited search for the best guess over the final set. Only the
+// `width` most promising guesses of the acceptable set are tried at every
+// node, sets that can't beat the best guess so far are cut off early and the
+// result of every (candidates, guesses left) pair is memoized.
+pub struct TreeSearch {
+    objective: Objective,
+    width: usize,
+    memo: HashMap<(WordSet, u32), (u32, Option<usize>)>,
+}
+
+impl TreeSearch {
+    pub fn new(objective: Objective, width: usize) -> TreeSearch {
+        TreeSearch {
+            objective,
+            width: width.max(1),
+            memo: HashMap::new(),
+        }
+    }
+
+    pub fn objective(&self) -> Objective {
+        self.objective
+    }
+
+    // the best guess (as a row of the table) for the candidates with the
+    // guesses left, and its cost
+    pub fn best_guess(
+        &mut self,
+        recommender: &Recommender,
+        candidates: &WordSet,
+        depth: u32,
+    ) -> Option<(usize, u32)> {
+        match self.solve(recommender, candidates, depth) {
+            (cost, Some(guess)) if cost < INF => Some((guess, cost)),
+            _ => None,
+        }
+    }
+
+    // the least cost to find every candidate, and the guess which gets it
+    fn solve(
+        &mut self,
+        recommender: &Recommender,
+        candidates: &WordSet,
+        depth: u32,
+    ) -> (u32, Option<usize>) {
+        let n = candidates.len() as u32;
+        if n == 0 {
+            return (0, None);
+        }
+        if depth == 0 {
+            return (INF, None);
+        }
+        let first = candidates
+            .iter()
+            .find_map(|i| recommender.row(&recommender.final_set()[i]));
+        if n == 1 {
+            return (1, first);
+        }
+        if n == 2 && depth >= 2 && first.is_some() {
+            // guess one of them, the other follows if it is wrong
+            return match self.objective {
+                Objective::Expected => (3, first),
+                Objective::WorstCase => (2, first),
+            };
+        }
+        let key = (candidates.clone(), depth);
+        if let Some(&result) = self.memo.get(&key) {
+            return result;
+        }
+        let mut best: (u32, Option<usize>) = (INF, None);
+        for guess in self.probes(recommender, candidates) {
+            let cost = self.cost_of(recommender, guess, candidates, depth, best.0);
+            if cost < best.0 {
+                best = (cost, Some(guess));
+            }
+        }
+        self.memo.insert(key, best);
+        best
+    }
+
+    // the cost of the guess, or INF once it can't be less than the bound
+    fn cost_of(
+        &mut self,
+        recommender: &Recommender,
+        guess: usize,
+        candidates: &WordSet,
+        depth: u32,
+        bound: u32,
+    ) -> u32 {
+        let mut buckets = TreeSearch::partition(recommender, guess, candidates);
+        buckets.retain(|(pattern, _)| !pattern.is_win());
+        buckets.sort_by_key(|(_, bucket)| std::cmp::Reverse(bucket.len()));
+        match self.objective {
+            Objective::Expected => {
+                // every candidate takes this guess, and at least two more if it is not the guess
+                let mut total = candidates.len() as u32
+                    + buckets
+                        .iter()
+                        .map(|(_, b)| TreeSearch::lower_bound(b.len() as u32))
+                        .sum::<u32>();
+                for (_, bucket) in &buckets {
+                    if total >= bound {
+                        return INF;
+                    }
+                    let (cost, _) = self.solve(recommender, bucket, depth - 1);
+                    if cost >= INF {
+                        return INF;
+                    }
+                    total = total - TreeSearch::lower_bound(bucket.len() as u32) + cost;
+                }
+                match total < bound {
+                    true => total,
+                    false => INF,
+                }
+            }
+            Objective::WorstCase => {
+                let mut worst = 1;
+                for (_, bucket) in &buckets {
+                    if worst >= bound {
+                        return INF;
+                    }
+                    let (cost, _) = self.solve(recommender, bucket, depth - 1);
+                    worst = worst.max(cost.saturating_add(1));
+                }
+                match worst < bound.min(INF) {
+                    true => worst,
+                    false => INF,
+                }
+            }
+        }
+    }
+
+    // the least total number of guesses to find n candidates
+    fn lower_bound(n: u32) -> u32 {
+        match n {
+            0 => 0,
+            _ => 2 * n - 1,
+        }
+    }
+
+    // the guesses worth trying: the ones leaving the smallest expected number
+    // of candidates, preferring the candidates themselves on ties
+    fn probes(&self, recommender: &Recommender, candidates: &WordSet) -> Vec<usize> {
+        let n = candidates.len() as u32;
+        let mut is_candidate = vec![false; recommender.acceptable_set().len()];
+        for i in candidates.iter() {
+            if let Some(row) = recommender.row(&recommender.final_set()[i]) {
+                is_candidate[row] = true;
+            }
+        }
+        let mut scored: Vec<(u64, bool, usize)> = vec![];
+        let mut cnt: Vec<u32> = vec![0; recommender.matrix().patterns()];
+        for (guess, &candidate) in is_candidate.iter().enumerate() {
+            let row = recommender.matrix().row(guess);
+            cnt.iter_mut().for_each(|c| *c = 0);
+            for answer in candidates.iter() {
+                cnt[row.code(answer)] += 1;
+            }
+            // a guess which doesn't split the candidates tells nothing
+            if cnt.contains(&n) && !candidate {
+                continue;
+            }
+            let squares: u64 = cnt.iter().map(|&c| (c as u64) * (c as u64)).sum();
+            scored.push((squares, !candidate, guess));
+        }
+        scored.sort_unstable();
+        scored
+            .into_iter()
+            .take(self.width)
+            .map(|(_, _, guess)| guess)
+            .collect()
+    }
+
+    // split the candidates by the pattern they give to the guess
+    fn partition(
+        recommender: &Recommender,
+        guess: usize,
+        candidates: &WordSet,
+    ) -> Vec<(Pattern, WordSet)> {
+        let row = recommender.matrix().row(guess);
+        let len = recommender.matrix().word_len();
+        let mut buckets: Vec<Option<WordSet>> = vec![None; recommender.matrix().patterns()];
+        for answer in candidates.iter() {
+            buckets[row.code(answer)]
+                .get_or_insert_with(|| WordSet::new(candidates.size()))
+                .insert(answer);
+        }
+        buckets
+            .into_iter()
+            .enumerate()
+            .filter_map(|(code, bucket)| Some((Pattern::from_code(code as u32, len)?, bucket?)))
+            .collect()
+    }
+
+    // the whole decision tree of the opener (a row of the table) over the
+    // candidates, None if some candidate can't be found in the guesses left
+    pub fn tree(
+        &mut self,
+        recommender: &Recommender,
+        opener: usize,
+        candidates: &WordSet,
+        depth: u32,
+    ) -> Option<DecisionTree> {
+        if depth == 0 {
+            return None;
+        }
+        let mut branches = vec![];
+        for (pattern, bucket) in TreeSearch::partition(recommender, opener, candidates) {
+            if pattern.is_win() {
+                branches.push((pattern, None));
+                continue;
+            }
+            let (guess, _) = self.best_guess(recommender, &bucket, depth - 1)?;
+            let child = self.tree(recommender, guess, &bucket, depth - 1)?;
+            branches.push((pattern, Some(child)));
+        }
+        Some(DecisionTree {
+            guess: recommender.acceptable_set()[opener].to_string(),
+            branches,
+        })
+    }
+}
+
+// what to guess next for every pattern, None once the guess is the answer
+#[derive(Debug, Clone)]
+pub struct DecisionTree {
+    pub guess: String,
+    pub branches: Vec<(Pattern, Option<DecisionTree>)>,
+}
+
+impl DecisionTree {
+    // one line for every answer, like "salet RRYRG crone GGGGG"
+    pub fn paths(&self) -> Vec<String> {
+        let mut lines = vec![];
+        for (pattern, child) in &self.branches {
+            let prefix = format!("{} {}", self.guess, pattern);
+            match child {
+                None => lines.push(prefix),
+                Some(child) => {
+                    for line in child.paths() {
+                        lines.push(format!("{} {}", prefix, line));
+                    }
+                }
+            }
+        }
+        lines
+    }
+
+    // the number of guesses of every answer
+    pub fn depths(&self) -> Vec<u32> {
+        let mut depths = vec![];
+        for (_, child) in &self.branches {
+            match child {
+                None => depths.push(1),
+                Some(child) => depths.extend(child.depths().into_iter().map(|d| d + 1)),
+            }
+        }
+        depths
+    }
+}
+
+impl fmt::Display for DecisionTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.paths() {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use crate::builtin_words;
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+// the words of a file with one word per line, in lower case, sorted and deduplicated.
+// A word of anything but the letters a to z is an InvalidData error.
+pub fn load_words(path: impl AsRef<Path>) -> io::Result<Vec<String>> {
+    let mut words = vec![];
+    for line in BufReader::new(File::open(path)?).lines() {
+        let word = line?.trim().to_lowercase();
+        if !word.bytes().all(|b| b.is_ascii_lowercase()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Wrong word '{}' in the word list.", word),
+            ));
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+    }
+    words.sort_unstable();
+    words.dedup();
+    Ok(words)
+}
+
+pub fn builtin_final() -> Vec<String> {
+    builtin_words::FINAL.iter().map(|s| s.to_string()).collect()
+}
+
+pub fn builtin_acceptable() -> Vec<String> {
+    builtin_words::ACCEPTABLE
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
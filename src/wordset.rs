@@ -0,0 +1,76 @@
+// a set of words given by their indexes in a word list, one bit per word
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WordSet {
+    bits: Vec<u64>,
+    size: usize,
+}
+
+impl WordSet {
+    // an empty set over a word list of the size
+    pub fn new(size: usize) -> WordSet {
+        WordSet {
+            bits: vec![0; size.div_ceil(64)],
+            size,
+        }
+    }
+
+    // the set of all words in the list
+    pub fn full(size: usize) -> WordSet {
+        let mut set = WordSet::new(size);
+        for (i, block) in set.bits.iter_mut().enumerate() {
+            let left = size - i * 64;
+            *block = match left >= 64 {
+                true => u64::MAX,
+                false => (1u64 << left) - 1,
+            };
+        }
+        set
+    }
+
+    // the size of the word list, not the number of words in the set
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&b| b == 0)
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        index < self.size && self.bits[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    pub fn insert(&mut self, index: usize) {
+        self.bits[index / 64] |= 1 << (index % 64);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.bits[index / 64] &= !(1 << (index % 64));
+    }
+
+    // keep the words which are also in the other set
+    pub fn intersect_with(&mut self, other: &WordSet) {
+        for (a, b) in self.bits.iter_mut().zip(other.bits.iter()) {
+            *a &= b;
+        }
+    }
+
+    // the indexes of the words in the set, in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.bits.iter().enumerate().flat_map(|(i, &block)| {
+            let mut block = block;
+            std::iter::from_fn(move || match block {
+                0 => None,
+                _ => {
+                    let bit = block.trailing_zeros() as usize;
+                    block &= block - 1;
+                    Some(i * 64 + bit)
+                }
+            })
+        })
+    }
+}
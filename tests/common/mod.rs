@@ -0,0 +1,15 @@
+// helpers shared by the tests
+use std::{env, fs, path::PathBuf};
+
+// an empty directory of the test, named after the test file and the process
+pub fn scratch(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!(
+        "wordle-{}-{}-{}",
+        env!("CARGO_CRATE_NAME"),
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -0,0 +1,166 @@
+// what the key word must look like after the patterns of the guesses
+use wordle::{
+    constraints::{Constraints, HardModeErr},
+    pattern::score,
+    wordset::WordSet,
+};
+
+fn applied(rows: &[(&str, &str)]) -> Constraints {
+    let mut constraints = Constraints::new();
+    for (guess, pattern) in rows {
+        constraints.apply(guess, pattern.parse().unwrap());
+    }
+    constraints
+}
+
+#[test]
+fn matches_greens_yellows_and_greys() {
+    let constraints = applied(&[("slate", "RRGRG")]);
+    assert!(constraints.matches("crane"));
+    assert!(constraints.matches("brace"));
+    // the A is green, the S is grey, the T is grey
+    assert!(!constraints.matches("crone"));
+    assert!(!constraints.matches("snare"));
+    assert!(!constraints.matches("crate"));
+
+    let constraints = applied(&[("round", "RYYRR")]);
+    assert!(constraints.matches("pious"));
+    // the O and the U are in the word but not where they were yellow
+    assert!(!constraints.matches("bouts"));
+    assert!(!constraints.matches("pixie"));
+}
+
+#[test]
+fn matches_letter_counts() {
+    // one E is yellow and the other grey, so there is exactly one E
+    let constraints = applied(&[("eerie", "YRRRR")]);
+    assert!(constraints.matches("abbey"));
+    assert!(!constraints.matches("belle"));
+    assert!(!constraints.matches("tangy"));
+    assert_eq!(constraints.min_count('e'), 1);
+    assert_eq!(constraints.max_count('e'), 1);
+    assert_eq!(constraints.max_count('r'), 0);
+
+    // two yellow L's need two L's
+    let constraints = applied(&[("llama", "GYYRR")]);
+    assert!(constraints.matches("label"));
+    assert!(!constraints.matches("lapse"));
+    assert_eq!(constraints.min_count('l'), 2);
+    assert_eq!(constraints.green(0), Some('l'));
+    assert!(constraints.forbidden(1, 'l'));
+}
+
+#[test]
+fn matches_what_the_answer_scores() {
+    // the answer always fits the patterns it gives
+    let words = [
+        "abbey", "crane", "eerie", "label", "llama", "speed", "abide",
+    ];
+    for answer in words {
+        let mut constraints = Constraints::new();
+        for guess in words {
+            constraints.apply(guess, score(guess, answer));
+            assert!(constraints.matches(answer), "{} after {}", answer, guess);
+        }
+    }
+    assert!(!applied(&[("crane", "GGGGG")]).matches("crank"));
+    assert!(Constraints::new().matches("xylyl"));
+}
+
+#[test]
+fn filter_and_retain() {
+    let words: Vec<String> = ["abbey", "crane", "pious", "round", "slate"]
+        .iter()
+        .map(|w| w.to_string())
+        .collect();
+    let constraints = applied(&[("salet", "RYRYR")]);
+    let set = constraints.filter(&words);
+    assert_eq!(set.iter().collect::<Vec<usize>>(), [1]);
+
+    let mut set = WordSet::full(words.len());
+    applied(&[("crane", "RRRRR")]).retain(&words, &mut set);
+    assert_eq!(set.iter().collect::<Vec<usize>>(), [2]);
+}
+
+#[test]
+fn hard_mode_keeps_greens_yellows_and_counts() {
+    let constraints = applied(&[("slate", "RRGRG")]);
+    assert_eq!(constraints.check_hard("brace", false), Ok(()));
+    assert_eq!(constraints.check_hard("trace", false), Ok(()));
+    assert_eq!(
+        constraints.check_hard("crone", false),
+        Err(HardModeErr::Green(2, 'a'))
+    );
+
+    let constraints = applied(&[("round", "RYYRR")]);
+    assert_eq!(constraints.check_hard("pious", false), Ok(()));
+    assert_eq!(
+        constraints.check_hard("bouts", false),
+        Err(HardModeErr::Yellow(1, 'o'))
+    );
+    assert_eq!(
+        constraints.check_hard("pixie", false),
+        Err(HardModeErr::TooFew('o', 1))
+    );
+
+    // a yellow and a green E, so two of them
+    let constraints = applied(&[("eerie", "YRRRG")]);
+    assert_eq!(constraints.check_hard("geese", false), Ok(()));
+    assert_eq!(
+        constraints.check_hard("shame", false),
+        Err(HardModeErr::TooFew('e', 2))
+    );
+}
+
+#[test]
+fn ultra_hard_mode_rejects_what_is_known_wrong() {
+    let constraints = applied(&[("slate", "RRGRG")]);
+    assert_eq!(constraints.check_hard("brace", true), Ok(()));
+    assert_eq!(
+        constraints.check_hard("trace", true),
+        Err(HardModeErr::TooMany('t', 0))
+    );
+
+    // the second E is grey, so there are exactly two
+    let constraints = applied(&[("eerie", "YRRRG")]);
+    assert_eq!(
+        constraints.check_hard("geese", true),
+        Err(HardModeErr::TooMany('e', 2))
+    );
+
+    // one E, which isn't the second letter
+    let constraints = applied(&[("eerie", "YRRRR")]);
+    assert_eq!(constraints.check_hard("beady", false), Ok(()));
+    assert_eq!(
+        constraints.check_hard("beady", true),
+        Err(HardModeErr::Forbidden(1, 'e'))
+    );
+}
+
+#[test]
+fn hard_mode_messages() {
+    assert_eq!(
+        HardModeErr::Green(2, 'a').to_string(),
+        "Letter 3 must be A."
+    );
+    assert_eq!(
+        HardModeErr::Yellow(1, 'o').to_string(),
+        "O was already yellow at letter 2, move it."
+    );
+    assert_eq!(
+        HardModeErr::TooFew('e', 2).to_string(),
+        "Guess must contain E at least twice."
+    );
+    assert_eq!(
+        HardModeErr::TooMany('t', 0).to_string(),
+        "T is not in the key word."
+    );
+    assert_eq!(
+        HardModeErr::TooMany('e', 3).to_string(),
+        "Guess can contain E at most 3 times."
+    );
+    assert_eq!(
+        HardModeErr::Forbidden(1, 'e').to_string(),
+        "E is known not to be letter 2."
+    );
+}
@@ -0,0 +1,75 @@
+// golden values of the calendar, a date must always be the same day and so the
+// same daily puzzle
+use wordle::date::{Date, ParseDateErr};
+
+fn date(s: &str) -> Date {
+    s.parse().unwrap()
+}
+
+#[test]
+fn epochs() {
+    assert_eq!(Date::from_unix_millis(0).to_string(), "1970-01-01");
+    assert_eq!(Date::from_unix_millis(0).ymd(), (1970, 1, 1));
+    assert_eq!(Date::WORDLE_EPOCH.to_string(), "2021-06-19");
+    assert_eq!(date("2021-06-19"), Date::WORDLE_EPOCH);
+    assert_eq!(date("2022-01-31").days_since(Date::WORDLE_EPOCH), 226);
+    assert_eq!(Date::WORDLE_EPOCH.add_days(226).to_string(), "2022-01-31");
+    // the last millisecond of a day is still that day
+    assert_eq!(
+        Date::from_unix_millis(1_643_673_599_999).to_string(),
+        "2022-01-31"
+    );
+    assert_eq!(
+        Date::from_unix_millis(1_643_673_600_000).to_string(),
+        "2022-02-01"
+    );
+}
+
+#[test]
+fn leap_days() {
+    assert_eq!(date("2024-02-29").add_days(1).to_string(), "2024-03-01");
+    assert_eq!(date("2023-02-28").add_days(1).to_string(), "2023-03-01");
+    assert_eq!(date("2000-02-28").add_days(1).to_string(), "2000-02-29");
+    assert_eq!(date("2000-03-01").days_since(date("2000-02-28")), 2);
+    assert_eq!(date("1900-03-01").days_since(date("1900-02-28")), 1);
+    assert_eq!(date("2024-01-01").days_since(date("2023-01-01")), 365);
+    assert_eq!(date("2025-01-01").days_since(date("2024-01-01")), 366);
+    // 1900 and 2100 aren't leap years, 2000 is
+    assert_eq!(Date::from_ymd(1900, 2, 29), None);
+    assert_eq!(Date::from_ymd(2100, 2, 29), None);
+    assert_eq!(Date::from_ymd(2023, 2, 29), None);
+    assert!(Date::from_ymd(2000, 2, 29).is_some());
+    assert_eq!("1900-02-29".parse::<Date>(), Err(ParseDateErr));
+}
+
+#[test]
+fn round_trip() {
+    let start = date("1600-01-01");
+    for days in (0..300_000).step_by(7) {
+        let d = start.add_days(days);
+        let (year, month, day) = d.ymd();
+        assert_eq!(Date::from_ymd(year, month, day), Some(d));
+        assert_eq!(date(&d.to_string()), d);
+    }
+    assert_eq!(date("1969-12-31").add_days(1), Date::from_unix_millis(0));
+}
+
+#[test]
+fn parse_errors() {
+    for s in [
+        "",
+        "2022-1-31",
+        "22-01-31",
+        "2022-01-31-",
+        "2022/01/31",
+        "2022-00-10",
+        "2022-13-01",
+        "2022-04-31",
+        "2022-01-00",
+        "2022-+1-31",
+        "2022-01-3a",
+        " 2022-01-31",
+    ] {
+        assert_eq!(s.parse::<Date>(), Err(ParseDateErr), "{:?}", s);
+    }
+}
@@ -0,0 +1,149 @@
+// the games and guesses of the state file as CSV, JSON Lines and Markdown
+use wordle::{
+    date::Date,
+    export::{game_rows, guess_rows, write, Filter, Format, Row},
+    state::{Game, Mode, State},
+};
+
+// a player whose name needs quoting in CSV and escaping in Markdown
+const PLAYER: &str = "al \"pipe|d\", jr";
+
+fn strings(words: &[&str]) -> Vec<String> {
+    words.iter().map(|w| w.to_string()).collect()
+}
+
+fn state() -> State {
+    let mut state = State::new();
+    state.default.games.push(Game {
+        id: "a".to_string(),
+        answer: "CRANE".to_string(),
+        guesses: strings(&["SLATE", "CRANE"]),
+        mode: Some(Mode::Date),
+        date: Some("2022-01-31".to_string()),
+        ..Game::new()
+    });
+    // neither a date nor the time it was over
+    state.default.games.push(Game {
+        id: "b".to_string(),
+        answer: "PIOUS".to_string(),
+        guesses: strings(&["ROUND"]),
+        mode: Some(Mode::Random),
+        ..Game::new()
+    });
+    // two boards, over on 2022-02-02
+    state.profile_mut(Some(PLAYER)).games.push(Game {
+        id: "c".to_string(),
+        answer: "CRANE".to_string(),
+        answers: strings(&["CRANE", "PIOUS"]),
+        guesses: strings(&["CRANE", "PIOUS"]),
+        mode: Some(Mode::Word),
+        finished: Some(1_643_760_000_000 + 3_600_000),
+        ..Game::new()
+    });
+    state
+}
+
+fn written<R: Row>(rows: &[R], format: Format) -> String {
+    let mut out = vec![];
+    write(rows, format, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+fn ids(state: &State, filter: &Filter) -> Vec<String> {
+    game_rows(state, filter).into_iter().map(|r| r.id).collect()
+}
+
+#[test]
+fn games_as_csv() {
+    assert_eq!(
+        written(&game_rows(&state(), &Filter::default()), Format::Csv),
+        "player,id,date,answer,guesses,result,guess_count,mode\n\
+         (default),a,2022-01-31,CRANE,SLATE CRANE,won,2,date\n\
+         (default),b,,PIOUS,ROUND,lost,1,random\n\
+         \"al \"\"pipe|d\"\", jr\",c,2022-02-02,CRANE PIOUS,CRANE PIOUS,won,2,word\n"
+    );
+}
+
+#[test]
+fn games_as_jsonl() {
+    let filter = Filter {
+        player: Some("(default)".to_string()),
+        ..Filter::default()
+    };
+    assert_eq!(
+        written(&game_rows(&state(), &filter), Format::Jsonl),
+        "{\"player\":\"(default)\",\"id\":\"a\",\"date\":\"2022-01-31\",\"answer\":\"CRANE\",\
+         \"guesses\":\"SLATE CRANE\",\"result\":\"won\",\"guess_count\":2,\"mode\":\"date\"}\n\
+         {\"player\":\"(default)\",\"id\":\"b\",\"date\":\"\",\"answer\":\"PIOUS\",\
+         \"guesses\":\"ROUND\",\"result\":\"lost\",\"guess_count\":1,\"mode\":\"random\"}\n"
+    );
+}
+
+#[test]
+fn games_as_markdown() {
+    let filter = Filter {
+        player: Some(PLAYER.to_string()),
+        ..Filter::default()
+    };
+    assert_eq!(
+        written(&game_rows(&state(), &filter), Format::Markdown),
+        "| player | id | date | answer | guesses | result | guess_count | mode |\n\
+         | --- | --- | --- | --- | --- | --- | --- | --- |\n\
+         | al \"pipe\\|d\", jr | c | 2022-02-02 | CRANE PIOUS | CRANE PIOUS | won | 2 | word |\n"
+    );
+}
+
+#[test]
+fn guesses_on_every_board() {
+    let rows = guess_rows(&state(), &Filter::default());
+    let patterns: Vec<(&str, &str)> = rows
+        .iter()
+        .map(|r| (r.guess.as_str(), r.pattern.as_str()))
+        .collect();
+    assert_eq!(
+        patterns,
+        [
+            ("SLATE", "RRGRG"),
+            ("CRANE", "GGGGG"),
+            ("ROUND", "RYYRR"),
+            ("CRANE", "GGGGG RRRRR"),
+            ("PIOUS", "RRRRR GGGGG"),
+        ]
+    );
+    assert_eq!(
+        written(&rows[3..], Format::Csv),
+        "player,id,date,answer,index,guess,pattern\n\
+         \"al \"\"pipe|d\"\", jr\",c,2022-02-02,CRANE PIOUS,1,CRANE,GGGGG RRRRR\n\
+         \"al \"\"pipe|d\"\", jr\",c,2022-02-02,CRANE PIOUS,2,PIOUS,RRRRR GGGGG\n"
+    );
+}
+
+#[test]
+fn filter_days() {
+    let state = state();
+    let day = |s: &str| Some(s.parse::<Date>().unwrap());
+    assert_eq!(ids(&state, &Filter::default()), ["a", "b", "c"]);
+    // both days are included, and a game without a day is left out
+    let filter = Filter {
+        from: day("2022-01-31"),
+        to: day("2022-02-02"),
+        ..Filter::default()
+    };
+    assert_eq!(ids(&state, &filter), ["a", "c"]);
+    let filter = Filter {
+        from: day("2022-02-01"),
+        ..Filter::default()
+    };
+    assert_eq!(ids(&state, &filter), ["c"]);
+    let filter = Filter {
+        to: day("2022-02-01"),
+        ..Filter::default()
+    };
+    assert_eq!(ids(&state, &filter), ["a"]);
+    let filter = Filter {
+        to: day("2022-01-30"),
+        player: Some("(default)".to_string()),
+        ..Filter::default()
+    };
+    assert!(ids(&state, &filter).is_empty());
+}
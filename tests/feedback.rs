@@ -0,0 +1,64 @@
+// the filter of lying games: every pattern but the winning one has exactly one
+// letter of a wrong color
+use wordle::{
+    feedback::{fits_one_lie, Knowledge},
+    pattern::Pattern,
+    strategy::History,
+};
+
+fn rows(rows: &[(&str, &str)]) -> Vec<(String, Pattern)> {
+    rows.iter()
+        .map(|(guess, pattern)| (guess.to_string(), pattern.parse().unwrap()))
+        .collect()
+}
+
+#[test]
+fn one_lie_per_row() {
+    // crane against pious is RRRRR, so one letter of another color
+    assert!(fits_one_lie("pious", &rows(&[("crane", "RRRYR")])));
+    assert!(fits_one_lie("pious", &rows(&[("crane", "GRRRR")])));
+    // the truth and two lies don't fit
+    assert!(!fits_one_lie("pious", &rows(&[("crane", "RRRRR")])));
+    assert!(!fits_one_lie("pious", &rows(&[("crane", "GYRRR")])));
+    // every row must fit
+    assert!(!fits_one_lie(
+        "pious",
+        &rows(&[("crane", "RRRYR"), ("round", "RYYRR")])
+    ));
+    assert!(fits_one_lie(
+        "pious",
+        &rows(&[("crane", "RRRYR"), ("round", "RYGRR")])
+    ));
+    assert!(fits_one_lie("pious", &[]));
+}
+
+#[test]
+fn rows_not_won_rule_out_their_guess() {
+    // crane would be all green, so a row one letter off it isn't its answer
+    assert!(!fits_one_lie("crane", &rows(&[("crane", "GGGGY")])));
+    assert!(!fits_one_lie("crane", &rows(&[("crane", "RGGGG")])));
+    assert!(!fits_one_lie(
+        "slate",
+        &rows(&[("crane", "RRGRG"), ("slate", "GGGGR")])
+    ));
+}
+
+#[test]
+fn winning_row_tells_the_truth() {
+    assert!(fits_one_lie("crane", &rows(&[("crane", "GGGGG")])));
+    // crate is one letter off, but the winning row never lies
+    assert!(!fits_one_lie("crate", &rows(&[("crane", "GGGGG")])));
+}
+
+#[test]
+fn knowledge_and_history_use_the_filter() {
+    let mut knowledge = Knowledge::lying();
+    knowledge.update("crane", "GGGGY".parse().unwrap());
+    assert!(!knowledge.check_possible("crane"));
+    assert!(knowledge.check_possible("crank"));
+
+    let mut history = History::lying();
+    history.push("crane", "GGGGY".parse().unwrap());
+    assert!(!history.possible("crane"));
+    assert!(history.possible("crank"));
+}
@@ -0,0 +1,79 @@
+// the cached table of the patterns of every guess against every answer
+use common::scratch;
+use std::{
+    fs::{self, File},
+    path::Path,
+    time::{Duration, SystemTime},
+};
+use wordle::{matrix::PatternMatrix, pattern::score};
+
+mod common;
+
+fn words(words: &[&str]) -> Vec<String> {
+    words.iter().map(|w| w.to_string()).collect()
+}
+
+fn names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn build_and_load() {
+    let dir = scratch("build");
+    let guesses = words(&["abbey", "crane", "eerie", "salet"]);
+    let answers = words(&["abbey", "crane", "pious"]);
+    for _ in 0..2 {
+        let matrix = PatternMatrix::load_or_build(&guesses, &answers, &dir).unwrap();
+        assert_eq!((matrix.guesses(), matrix.answers()), (4, 3));
+        for (g, guess) in guesses.iter().enumerate() {
+            for (a, answer) in answers.iter().enumerate() {
+                assert_eq!(matrix.get(g, a), score(guess, answer));
+            }
+        }
+    }
+    let key = PatternMatrix::cache_key(&guesses, &answers);
+    assert_eq!(names(&dir), [format!("patterns-{:016x}.bin", key)]);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn sweep_interrupted_builds() {
+    let dir = scratch("sweep");
+    // left by a build interrupted long ago, and one still being written
+    let old = File::create(dir.join("patterns-0000000000000001.4242.tmp")).unwrap();
+    old.set_modified(SystemTime::now() - Duration::from_secs(3600))
+        .unwrap();
+    File::create(dir.join("patterns-0000000000000002.4343.tmp")).unwrap();
+    File::create(dir.join("other.tmp")).unwrap();
+
+    let guesses = words(&["crane", "salet"]);
+    PatternMatrix::load_or_build(&guesses, &guesses, &dir).unwrap();
+    let key = PatternMatrix::cache_key(&guesses, &guesses);
+    assert_eq!(
+        names(&dir),
+        [
+            "other.tmp".to_string(),
+            "patterns-0000000000000002.4343.tmp".to_string(),
+            format!("patterns-{:016x}.bin", key)
+        ]
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn failed_build_leaves_nothing() {
+    let dir = scratch("failed");
+    let guesses = words(&["crane", "salet"]);
+    let key = PatternMatrix::cache_key(&guesses, &guesses);
+    // the table can't be renamed over a directory
+    fs::create_dir(dir.join(format!("patterns-{:016x}.bin", key))).unwrap();
+    fs::write(dir.join(format!("patterns-{:016x}.bin", key)).join("x"), "").unwrap();
+    assert!(PatternMatrix::load_or_build(&guesses, &guesses, &dir).is_err());
+    assert_eq!(names(&dir), [format!("patterns-{:016x}.bin", key)]);
+    fs::remove_dir_all(dir).unwrap();
+}
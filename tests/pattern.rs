@@ -0,0 +1,97 @@
+// the feedback of a guess, duplicate letters are yellow only while the answer
+// has copies of them not matched yet
+use wordle::{
+    pattern::{score, ParsePatternErr, Pattern},
+    shuffle::SplitMix64,
+};
+
+fn scored(guess: &str, answer: &str) -> String {
+    score(guess, answer).to_string()
+}
+
+#[test]
+fn score_greens_and_yellows() {
+    assert_eq!(scored("crane", "crane"), "GGGGG");
+    assert_eq!(scored("crane", "pious"), "RRRRR");
+    assert_eq!(scored("slate", "crane"), "RRGRG");
+    assert_eq!(scored("round", "pious"), "RYYRR");
+    assert!(score("crane", "crane").is_win());
+    assert!(!score("slate", "crane").is_win());
+}
+
+#[test]
+fn score_duplicate_letters() {
+    // one E in the answer, only the first extra E is yellow
+    assert_eq!(scored("eerie", "abbey"), "YRRRR");
+    // the green L takes one L, the other is yellow, the second A has no A left
+    assert_eq!(scored("llama", "label"), "GYYRR");
+    assert_eq!(scored("speed", "abide"), "RRYRY");
+    // a green later in the word takes the copy before a yellow could
+    assert_eq!(scored("geese", "those"), "RRRGG");
+    assert_eq!(scored("abbey", "babes"), "YYGGR");
+}
+
+#[test]
+fn score_other_lengths() {
+    assert_eq!(scored("ball", "lobe"), "YRYR");
+    assert_eq!(scored("worded", "wonder"), "GGYGGR");
+    assert_eq!(score("worded", "wonder").len(), 6);
+}
+
+#[test]
+fn pattern_round_trip() {
+    for text in ["GYRRR", "RRRRR", "GGGGG", "YRYG", "GGGGGGGGGGG"] {
+        let pattern: Pattern = text.parse().unwrap();
+        assert_eq!(pattern.to_string(), text);
+        assert_eq!(pattern.len(), text.len());
+        assert_eq!(
+            Pattern::from_code(pattern.code(), pattern.len()),
+            Some(pattern)
+        );
+    }
+    assert_eq!("gyrrr".parse::<Pattern>().unwrap().to_string(), "GYRRR");
+    assert!("GGGGG".parse::<Pattern>().unwrap().is_win());
+    assert_eq!(Pattern::all_green(5).to_string(), "GGGGG");
+}
+
+#[test]
+fn pattern_parse_errors() {
+    assert_eq!("GYR".parse::<Pattern>(), Err(ParsePatternErr));
+    assert_eq!("GGGGGGGGGGGG".parse::<Pattern>(), Err(ParsePatternErr));
+    assert_eq!("GYRXR".parse::<Pattern>(), Err(ParsePatternErr));
+    assert_eq!("".parse::<Pattern>(), Err(ParsePatternErr));
+}
+
+#[test]
+fn pattern_codes() {
+    // the first letter is the lowest digit
+    assert_eq!("YRRRR".parse::<Pattern>().unwrap().code(), 1);
+    assert_eq!("RGRRR".parse::<Pattern>().unwrap().code(), 6);
+    assert_eq!(Pattern::all_green(5).code(), 242);
+    assert_eq!(Pattern::from_code(243, 5), None);
+    assert_eq!(Pattern::from_code(0, 3), None);
+    let a: Pattern = "GYRRY".parse().unwrap();
+    let b: Pattern = "GRRGY".parse().unwrap();
+    assert_eq!(a.distance(&b), 2);
+}
+
+#[test]
+fn lies_of_a_seed() {
+    // golden values: a seed must show the same lies in every version
+    let mut rng = SplitMix64::new(0);
+    let lies: Vec<String> = ["RRGRG", "RRRRR", "GGGGY", "RYYRR"]
+        .iter()
+        .map(|p| p.parse::<Pattern>().unwrap().lie(&mut rng).to_string())
+        .collect();
+    assert_eq!(lies, ["RRYRG", "YRRRR", "YGGGY", "RYRRR"]);
+    // one letter of another color, and never a win
+    let mut rng = SplitMix64::new(42);
+    for code in 0..243 {
+        let pattern = Pattern::from_code(code, 5).unwrap();
+        if !pattern.is_win() {
+            let lie = pattern.lie(&mut rng);
+            assert_eq!(pattern.distance(&lie), 1);
+            assert!(!lie.is_win());
+        }
+    }
+}
@@ -0,0 +1,49 @@
+// the words the recommender offers as the next guess
+use common::scratch;
+use std::fs;
+use wordle::{
+    constraints::Constraints,
+    recommend::{Probes, Recommender},
+};
+
+mod common;
+
+fn applied(guess: &str, pattern: &str) -> Constraints {
+    let mut constraints = Constraints::new();
+    constraints.apply(guess, pattern.parse().unwrap());
+    constraints
+}
+
+#[test]
+fn hard_mode_probes_keep_to_every_board() {
+    let dir = scratch("hard");
+    let words: Vec<String> = ["brace", "crane", "crate", "grace", "slate", "trace"]
+        .iter()
+        .map(|w| w.to_string())
+        .collect();
+    let recommender = Recommender::new(words.clone(), words.clone(), &dir).unwrap();
+    let candidates = recommender.candidates(|_| true);
+    let probes = |boards: &[&Constraints]| -> Vec<&str> {
+        recommender
+            .probes(&candidates, Probes::AnyWord, Some((boards, false)))
+            .iter()
+            .map(|row| words[row].as_str())
+            .collect()
+    };
+    // the A and the E in place
+    let first = applied("slate", "RRGRG");
+    // RACE in place
+    let second = applied("brace", "RGGGG");
+    // a T, which isn't the first letter
+    let third = applied("tight", "YRRRR");
+    assert_eq!(probes(&[&first]).len(), words.len());
+    assert_eq!(probes(&[&second]), ["brace", "grace", "trace"]);
+    assert_eq!(probes(&[&third]), ["crate", "slate"]);
+    assert_eq!(probes(&[&first, &third]), ["crate", "slate"]);
+    assert!(probes(&[&second, &third]).is_empty());
+    assert_eq!(
+        recommender.probes(&candidates, Probes::AnyWord, None).len(),
+        words.len()
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
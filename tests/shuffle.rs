@@ -0,0 +1,71 @@
+// golden values of the answer order, a seed and a day must give the same word
+// forever, so these only change along with the algorithm in src/shuffle.rs
+use wordle::{
+    shuffle::{nth_answer, shuffle, SplitMix64},
+    wordlist,
+};
+
+// the default seed of the game
+const SEED: u64 = 19260817998244353;
+
+#[test]
+fn splitmix64_numbers() {
+    let mut rng = SplitMix64::new(0);
+    assert_eq!(rng.next_u64(), 0xe220a8397b1dcdaf);
+    assert_eq!(rng.next_u64(), 0x6e789e6aa1b965f4);
+    assert_eq!(rng.next_u64(), 0x06c45d188009454f);
+
+    let mut rng = SplitMix64::new(1234567);
+    assert_eq!(rng.next_u64(), 0x599ed017fb08fc85);
+    assert_eq!(rng.next_u64(), 0x2c73f08458540fa5);
+    assert_eq!(rng.next_u64(), 0x883ebce5a3f27c77);
+}
+
+#[test]
+fn below_bound() {
+    let mut rng = SplitMix64::new(SEED);
+    for bound in 1..100 {
+        assert!(rng.below(bound) < bound);
+    }
+    assert_eq!(SplitMix64::new(7).below(1), 0);
+}
+
+#[test]
+fn shuffle_order() {
+    let mut items: Vec<u32> = (0..10).collect();
+    shuffle(&mut items, 0);
+    assert_eq!(items, [6, 3, 2, 9, 8, 1, 4, 7, 0, 5]);
+
+    let mut items: Vec<u32> = (0..10).collect();
+    shuffle(&mut items, 42);
+    assert_eq!(items, [0, 9, 5, 8, 6, 4, 7, 2, 1, 3]);
+
+    let mut empty: Vec<u32> = vec![];
+    shuffle(&mut empty, 0);
+    assert!(empty.is_empty());
+}
+
+fn answers(seed: u64) -> Vec<String> {
+    let final_set = wordlist::builtin_final();
+    assert_eq!(final_set.len(), 2315);
+    [0, 1, 2, 3, 4, 100, 1000, 2316]
+        .iter()
+        .map(|&day| nth_answer(&final_set, seed, day).to_string())
+        .collect()
+}
+
+#[test]
+fn builtin_answers() {
+    assert_eq!(
+        answers(SEED),
+        ["colon", "lever", "retro", "caput", "nurse", "bayou", "sixth", "lever"]
+    );
+    assert_eq!(
+        answers(0),
+        ["leach", "hovel", "round", "crash", "llama", "rally", "rearm", "hovel"]
+    );
+    assert_eq!(
+        answers(2022),
+        ["needy", "prawn", "reedy", "often", "nerdy", "couch", "rayon", "prawn"]
+    );
+}
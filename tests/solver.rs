@@ -0,0 +1,97 @@
+// runs of the wordle_solver binary on a few final words, which keep the pattern
+// table small
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+};
+
+const FINAL: &str = "abbey\ncrane\npious\nround\nslate\n";
+
+mod common;
+
+// a directory of the test with the final set in it
+fn scratch(name: &str) -> PathBuf {
+    let dir = common::scratch(name);
+    fs::write(dir.join("final.txt"), FINAL).unwrap();
+    dir
+}
+
+// the solver run with the input typed in
+fn solver(dir: &Path, args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_wordle_solver"))
+        .args(args)
+        .arg("-f")
+        .arg(dir.join("final.txt"))
+        .env("XDG_CACHE_HOME", dir.join("cache"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn bench() {
+    let dir = scratch("bench");
+    let csv = dir.join("bench.csv");
+    let output = solver(
+        &dir,
+        &["bench", "--opener", "salet", "--csv", csv.to_str().unwrap()],
+        "",
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("SALET with entropy: 5 words, 5 solved"));
+    let csv = fs::read_to_string(csv).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("answer,guesses,solved,path"));
+    assert_eq!(lines.next(), Some("abbey,2,true,salet abbey"));
+    assert_eq!(lines.count(), 4);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn tree() {
+    let dir = scratch("tree");
+    let output = solver(&dir, &["-T", "salet"], "");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 5);
+    assert!(stdout.contains("salet GYYYY slate GGGGG\n"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+// the solver stops asking for patterns once the input ends
+#[test]
+#[ntest::timeout(10000)]
+fn interactive_until_the_input_ends() {
+    let dir = scratch("interactive");
+    let output = solver(&dir, &[], "salet\nRRRRR\n");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("I recommend you use: ROUND"));
+    assert!(!stdout.contains("Make sure"));
+
+    let output = solver(&dir, &["--max-guesses", "2"], "salet\nRRRRR\nRRRRR\n");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.ends_with("FAILED, there are no guesses left.\n"));
+    fs::remove_dir_all(dir).unwrap();
+}
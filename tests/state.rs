@@ -0,0 +1,281 @@
+// the state file: merging the games other processes saved to it, and reading
+// the files of older versions
+use std::{fs, path::PathBuf};
+use wordle::state::{Game, Profile, State};
+
+mod common;
+
+fn game(id: &str, answer: &str) -> Game {
+    Game {
+        id: id.to_string(),
+        answer: answer.to_string(),
+        guesses: vec![answer.to_string()],
+        ..Game::new()
+    }
+}
+
+fn ids(profile: &Profile) -> Vec<&str> {
+    profile.games.iter().map(|g| g.id.as_str()).collect()
+}
+
+// a state file of the test in a directory of its own
+fn scratch(name: &str) -> PathBuf {
+    common::scratch(name).join("state.json")
+}
+
+#[test]
+fn merge_overlapping_ids() {
+    let mut profile = Profile {
+        total_rounds: 2,
+        games: vec![game("a", "CRANE"), game("b", "SLATE")],
+        current: None,
+    };
+    let saved = Profile {
+        total_rounds: 2,
+        games: vec![game("b", "SLATE"), game("c", "PIOUS")],
+        current: None,
+    };
+    profile.merge(saved);
+    // the saved games first, then the ones only this profile has
+    assert_eq!(ids(&profile), ["b", "c", "a"]);
+    assert_eq!(profile.total_rounds, 3);
+    assert!(profile.is_consistent());
+}
+
+#[test]
+fn merge_games_without_ids() {
+    // games of old files have no id, and this profile read them from the file
+    let mut profile = Profile {
+        total_rounds: 2,
+        games: vec![game("", "CRANE"), game("a", "SLATE")],
+        current: None,
+    };
+    let saved = Profile {
+        total_rounds: 2,
+        games: vec![game("", "CRANE"), game("", "ROUND")],
+        current: None,
+    };
+    profile.merge(saved);
+    assert_eq!(ids(&profile), ["", "", "a"]);
+    let answers: Vec<&str> = profile.games.iter().map(|g| g.answer.as_str()).collect();
+    assert_eq!(answers, ["CRANE", "ROUND", "SLATE"]);
+    assert_eq!(profile.total_rounds, 3);
+}
+
+#[test]
+fn merge_current_round() {
+    // a saved round which is over isn't unfinished any more
+    let mut profile = Profile {
+        total_rounds: 1,
+        games: vec![game("x", "CRANE")],
+        current: None,
+    };
+    profile.merge(Profile {
+        total_rounds: 0,
+        games: vec![],
+        current: Some(game("x", "CRANE")),
+    });
+    assert!(profile.current.is_none());
+    assert_eq!(ids(&profile), ["x"]);
+
+    // one still unfinished is kept
+    let mut profile = Profile::new();
+    profile.merge(Profile {
+        total_rounds: 0,
+        games: vec![],
+        current: Some(game("y", "SLATE")),
+    });
+    assert_eq!(profile.current.map(|c| c.id), Some("y".to_string()));
+
+    // unless this profile has one of its own
+    let mut profile = Profile {
+        current: Some(game("z", "PIOUS")),
+        ..Profile::new()
+    };
+    profile.merge(Profile {
+        total_rounds: 0,
+        games: vec![],
+        current: Some(game("y", "SLATE")),
+    });
+    assert_eq!(profile.current.map(|c| c.id), Some("z".to_string()));
+}
+
+#[test]
+fn merge_players() {
+    let mut state = State::new();
+    state.default.games.push(game("a", "CRANE"));
+    state
+        .profile_mut(Some("alice"))
+        .games
+        .push(game("b", "SLATE"));
+    let mut saved = State::new();
+    saved
+        .profile_mut(Some("alice"))
+        .games
+        .push(game("c", "PIOUS"));
+    saved
+        .profile_mut(Some("bob"))
+        .games
+        .push(game("d", "ROUND"));
+    state.merge(saved);
+    assert_eq!(ids(&state.default), ["a"]);
+    assert_eq!(ids(&state.players["alice"]), ["c", "b"]);
+    assert_eq!(ids(&state.players["bob"]), ["d"]);
+    assert!(state.is_consistent());
+}
+
+#[test]
+fn save_keeps_the_games_of_others() {
+    let path = scratch("save");
+    let mut first = State::new();
+    first.default.games.push(game("a", "CRANE"));
+    first.default.total_rounds = 1;
+    first.save(&path).unwrap();
+
+    // another process which loaded the file before the first game was saved
+    let mut second = State::new();
+    second.default.games.push(game("b", "SLATE"));
+    second.default.total_rounds = 1;
+    second.save(&path).unwrap();
+    assert_eq!(ids(&second.default), ["a", "b"]);
+
+    let saved: State = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(ids(&saved.default), ["a", "b"]);
+    assert_eq!(saved.default.total_rounds, 2);
+    // only the state file and its lock are left
+    let dir = path.parent().unwrap();
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["state.json", "state.json.lock"]);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn migrate_version_1() {
+    // a file of version 1 has no version, no settings and no ids
+    let v1 = r#"{"total_rounds":2,"games":[
+        {"answer":"CRANE","guesses":["SLATE","CRANE"]},
+        {"answer":"PIOUS","guesses":["ROUND"]}]}"#;
+    let mut state: State = serde_json::from_str(v1).unwrap();
+    assert_eq!(state.version, 0);
+    state.migrate().unwrap();
+    assert_eq!(state.version, State::VERSION);
+    assert!(state.is_consistent());
+    assert!(state.players.is_empty());
+    assert!(state.default.games.iter().all(|g| g.mode.is_none()));
+
+    let path = scratch("migrate");
+    fs::write(&path, v1).unwrap();
+    state.save(&path).unwrap();
+    let text = fs::read_to_string(&path).unwrap();
+    let saved: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(saved["version"], 3);
+    assert_eq!(saved["total_rounds"], 2);
+    assert_eq!(
+        saved["games"][0],
+        serde_json::json!({"answer": "CRANE", "guesses": ["SLATE", "CRANE"]})
+    );
+    assert!(saved.get("players").is_none());
+
+    let mut reloaded: State = serde_json::from_str(&text).unwrap();
+    reloaded.migrate().unwrap();
+    assert_eq!(reloaded.default.games.len(), 2);
+    assert!(!reloaded.default.games[1].won());
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn migrate_newer_version() {
+    let mut state: State = serde_json::from_str(r#"{"version":4}"#).unwrap();
+    assert!(state.migrate().is_err());
+}
+
+#[test]
+fn leaderboard_ranking() {
+    let lost = |id: &str| Game {
+        guesses: vec!["SLATE".to_string()],
+        ..game(id, "CRANE")
+    };
+    let mut state = State::new();
+    // only an unfinished round, so no game played
+    state.profile_mut(Some("aaron")).current = Some(lost("a"));
+    state
+        .profile_mut(Some("alice"))
+        .games
+        .push(game("b", "CRANE"));
+    state.profile_mut(Some("bob")).games.push(lost("c"));
+    // as many wins as alice, in more guesses
+    let carol = state.profile_mut(Some("carol"));
+    carol.games.push(Game {
+        guesses: vec!["SLATE".to_string(), "CRANE".to_string()],
+        ..game("d", "CRANE")
+    });
+    // half won, then ties broken by the name
+    for name in ["dave", "dan"] {
+        let profile = state.profile_mut(Some(name));
+        profile.games.push(lost("e"));
+        profile.games.push(game("f", "CRANE"));
+    }
+    let names: Vec<&str> = state
+        .leaderboard()
+        .iter()
+        .map(|(name, _)| name.unwrap())
+        .collect();
+    assert_eq!(names, ["alice", "carol", "dan", "dave", "bob", "aaron"]);
+}
+
+#[test]
+fn stats_of_mixed_games() {
+    // won in the number of guesses
+    let won = |guesses: usize| Game {
+        guesses: [
+            vec!["SLATE".to_string(); guesses - 1],
+            vec!["CRANE".to_string()],
+        ]
+        .concat(),
+        ..game("", "CRANE")
+    };
+    let lost = Game {
+        guesses: vec!["CRANE".to_string(); 6],
+        ..game("", "SLATE")
+    };
+    // two boards, one of them solved
+    let lost_boards = Game {
+        answers: vec!["CRANE".to_string(), "PIOUS".to_string()],
+        guesses: vec![
+            "SLATE".to_string(),
+            "CRANE".to_string(),
+            "ROUND".to_string(),
+        ],
+        ..game("", "CRANE")
+    };
+    let profile = Profile {
+        total_rounds: 8,
+        games: vec![
+            won(2),
+            won(3),
+            won(3),
+            lost_boards,
+            won(1),
+            won(8),
+            lost,
+            won(4),
+        ],
+        current: None,
+    };
+    let stats = profile.stats();
+    assert_eq!((stats.played, stats.won, stats.guesses), (8, 6, 21));
+    assert_eq!((stats.current_streak, stats.max_streak), (1, 3));
+    // past 6 guesses the distribution grows
+    assert_eq!(stats.distribution, [1, 1, 2, 1, 0, 0, 0, 1]);
+    assert_eq!(stats.failed, ["PIOUS", "SLATE"]);
+    assert_eq!(stats.win_percent(), 75);
+    assert_eq!(stats.average_guesses(), 3.5);
+
+    let stats = Profile::new().stats();
+    assert_eq!(stats.distribution, [0; 6]);
+    assert_eq!((stats.win_percent(), stats.average_guesses()), (0, 0.0));
+}
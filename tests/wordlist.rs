@@ -0,0 +1,27 @@
+// word lists loaded from files
+use common::scratch;
+use std::{fs, io};
+use wordle::wordlist::load_words;
+
+mod common;
+
+#[test]
+fn load_sorted_lowercase_words() {
+    let dir = scratch("load");
+    let path = dir.join("words.txt");
+    fs::write(&path, "Slate\ncrane\n\n  pious \ncrane\n").unwrap();
+    assert_eq!(load_words(&path).unwrap(), ["crane", "pious", "slate"]);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn reject_words_of_other_letters() {
+    let dir = scratch("reject");
+    let path = dir.join("words.txt");
+    for word in ["ab-cd", "crän", "a1bcd", "two words"] {
+        fs::write(&path, format!("crane\n{}\n", word)).unwrap();
+        let err = load_words(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{}", word);
+    }
+    fs::remove_dir_all(dir).unwrap();
+}